@@ -0,0 +1,55 @@
+use bitcoin_rpc_errors::{Error, MiscError, TypeError, VerifyRejectedError};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+// Compare a change with `cargo bench --bench parse -- --save-baseline before` on the tree
+// without it and `cargo bench --bench parse -- --baseline before` with it. Before the
+// patterns were compiled once into a RegexSet (this file run on bec169e) against after:
+//   Error::from_str rejections           1.32 ms -> 6.98 us
+//   MiscError::from_str last variant     1.25 ms -> 305 ns
+//   MiscError::from_str fallback         1.08 ms -> 93.2 ns
+//   TypeError::from_str captures         171 us  -> 3.10 us
+//   VerifyRejectedError::from_str nested 111 us  -> 2.81 us
+
+// Rejections as the mempool monitor sees them from sendrawtransaction
+const REJECTIONS: [&str; 4] = [
+    r#"{"code":-26,"message":"min relay fee not met, 100 < 141"}"#,
+    r#"{"code":-26,"message":"mandatory-script-verify-flag-failed (Signature must be zero for failed CHECK(MULTI)SIG operation)"}"#,
+    r#"{"code":-25,"message":"bad-txns-inputs-missingorspent"}"#,
+    r#"{"code":-27,"message":"Transaction already in block chain"}"#,
+];
+
+fn parse_error(c: &mut Criterion) {
+    c.bench_function("Error::from_str rejections", |b| {
+        b.iter(|| {
+            for rejection in REJECTIONS {
+                let _: Result<Error, _> = black_box(rejection).parse();
+            }
+        })
+    });
+}
+
+fn parse_enum_error(c: &mut Criterion) {
+    // WalletUnloaded is the last variant of MiscError with patterns, every earlier variant is checked first
+    c.bench_function("MiscError::from_str last variant", |b| {
+        b.iter(|| black_box("Requested wallet already unloaded").parse::<MiscError>())
+    });
+
+    // No pattern matches and the fallback is used
+    c.bench_function("MiscError::from_str fallback", |b| {
+        b.iter(|| black_box("Unknown error").parse::<MiscError>())
+    });
+
+    c.bench_function("TypeError::from_str captures", |b| {
+        b.iter(|| black_box("JSON value of type string for field amount is not of expected type number").parse::<TypeError>())
+    });
+
+    c.bench_function("VerifyRejectedError::from_str nested", |b| {
+        b.iter(|| {
+            black_box("mandatory-script-verify-flag-failed (Script evaluated without error but finished with a false/empty top stack element)")
+                .parse::<VerifyRejectedError>()
+        })
+    });
+}
+
+criterion_group!(benches, parse_error, parse_enum_error);
+criterion_main!(benches);
@@ -0,0 +1,415 @@
+use proc_macro2::{Ident, TokenStream};
+use quote::{quote, quote_spanned};
+use syn::{spanned::Spanned, Data, DataEnum, Fields, LitInt, Type, Variant};
+
+use crate::{get_attribute_with_name_from_variant, is_fallback_variant, not_an_enum, push_error, remediation, retry};
+
+const CATEGORIES: [&str; 4] = ["General", "P2pClient", "Chain", "Wallet"];
+
+// `#[code(-13, category = Wallet)]`, the category is one of the groups of codes in protocol.h
+fn get_code_from_variant(variant: &Variant) -> syn::Result<Option<(i32, Ident)>> {
+    let attr = match get_attribute_with_name_from_variant(variant, "code") {
+        Some(attr) => attr,
+        None => return Ok(None),
+    };
+
+    let (code, category) = attr.parse_args_with(|input: syn::parse::ParseStream| {
+        let code: LitInt = input.parse()?;
+        if input.is_empty() {
+            return Ok((code, None));
+        }
+        input.parse::<syn::Token![,]>()?;
+        let key: Ident = input.parse()?;
+        if key != "category" {
+            return Err(syn::Error::new_spanned(key, "unknown argument, expected `category`"));
+        }
+        input.parse::<syn::Token![=]>()?;
+        let category: Ident = input.parse()?;
+        Ok((code, Some(category)))
+    })?;
+    let code: i32 = code.base10_parse()?;
+
+    let category = category.ok_or_else(|| {
+        syn::Error::new_spanned(
+            attr,
+            format!("Code {} of Variant {} needs a category, e.g. `#[code({}, category = General)]`", code, &variant.ident, code),
+        )
+    })?;
+    if !CATEGORIES.iter().any(|name| category == name) {
+        return Err(syn::Error::new_spanned(
+            category,
+            "unknown category, expected `General`, `P2pClient`, `Chain` or `Wallet`",
+        ));
+    }
+
+    Ok(Some((code, category)))
+}
+
+// A coded variant either ignores the message or parses it into its only field
+fn check_coded_variant(variant: &Variant) -> syn::Result<()> {
+    if variant.fields.len() > 1 || matches!(variant.fields, Fields::Named(_)) {
+        return Err(syn::Error::new_spanned(
+            &variant.fields,
+            format!("Variant {} can only have one unnamed field for the parsed message", &variant.ident),
+        ));
+    }
+
+    Ok(())
+}
+
+// The fallback keeps the code and the message of unknown errors
+fn check_fallback_variant(variant: &Variant) -> syn::Result<()> {
+    if get_attribute_with_name_from_variant(variant, "code").is_some() {
+        return Err(syn::Error::new_spanned(
+            &variant.ident,
+            format!("The fallback Variant {} can not have a code", &variant.ident),
+        ));
+    }
+
+    // Nothing is known about an unknown code, it is never retried
+    if let Some(attr) = get_attribute_with_name_from_variant(variant, "retry") {
+        return Err(syn::Error::new_spanned(
+            attr,
+            format!("The fallback Variant {} is always permanent, it can not have a retry class", &variant.ident),
+        ));
+    }
+    if let Some(attr) = get_attribute_with_name_from_variant(variant, "remediation") {
+        return Err(syn::Error::new_spanned(
+            attr,
+            format!("The fallback Variant {} has an unknown code, it can not have a remediation", &variant.ident),
+        ));
+    }
+
+    match &variant.fields {
+        Fields::Unnamed(fields) if fields.unnamed.len() == 2 => Ok(()),
+        _ => Err(syn::Error::new_spanned(
+            &variant.ident,
+            format!("The fallback Variant {} needs two fields for the code and the message", &variant.ident),
+        )),
+    }
+}
+
+// With a version the message is only matched against the patterns of that Core version.
+// The field type needs a `#[fallback]`, so a known code always keeps its variant.
+fn gen_from_code_arm(enum_name: &Ident, variant: &Variant, code: i32, versioned: bool) -> TokenStream {
+    let variant_name = &variant.ident;
+    let field = match variant.fields.iter().next() {
+        Some(field) => field,
+        None => return quote! { #code => #enum_name::#variant_name, },
+    };
+
+    let ty = &field.ty;
+    let version = if versioned {
+        quote! { Some(version) }
+    } else {
+        quote! { None }
+    };
+    // Only an enum with a fallback can not fail to parse, point at the field if it has none
+    let parsed = quote_spanned! {ty.span()=>
+        <#ty as ::bitcoin_rpc_errors::__private::WithFallback>::parse_message(message, #version)
+    };
+
+    quote! {
+        #code => #enum_name::#variant_name(#parsed),
+    }
+}
+
+fn gen_code_info(
+    variant: &Variant,
+    code: i32,
+    category: &Ident,
+    remediation: &Option<TokenStream>,
+    retry: &Option<TokenStream>,
+) -> TokenStream {
+    let name = variant.ident.to_string();
+    let (enum_name, variants) = match variant.fields.iter().next() {
+        Some(field) => {
+            let ty = &field.ty;
+            // The last segment of a path like `crate::MiscError`
+            let enum_name = match ty {
+                Type::Path(path) => path.path.segments.last().map(|segment| segment.ident.to_string()),
+                _ => None,
+            }
+            .unwrap_or_else(|| quote!(#ty).to_string());
+            (quote! { Some(#enum_name) }, quote! { <#ty>::VARIANTS })
+        }
+        None => (quote! { None }, quote! { &[] }),
+    };
+
+    let remediation = match remediation {
+        Some(remediation) => quote! { Some(#remediation) },
+        None => quote! { None },
+    };
+    let retry = match retry {
+        Some(hint) => quote! { Some(#hint) },
+        None => quote! { None },
+    };
+
+    quote! {
+        ::bitcoin_rpc_errors::CodeInfo {
+            name: #name,
+            code: #code,
+            category: ::bitcoin_rpc_errors::ErrorCategory::#category,
+            enum_name: #enum_name,
+            variants: #variants,
+            remediation: #remediation,
+            retry: #retry,
+        },
+    }
+}
+
+// A variant with its code and category
+type Coded<'a> = (&'a Variant, i32, Ident);
+
+fn check_variants<'a>(enum_name: &Ident, data_enum: &'a DataEnum) -> syn::Result<(Vec<Coded<'a>>, &'a Variant)> {
+    let mut errors: Option<syn::Error> = None;
+    let mut coded: Vec<Coded> = Vec::new();
+    let mut fallback: Option<&Variant> = None;
+
+    for variant in &data_enum.variants {
+        if is_fallback_variant(variant) {
+            if let Err(error) = check_fallback_variant(variant) {
+                push_error(&mut errors, error);
+            }
+            if fallback.is_some() {
+                push_error(
+                    &mut errors,
+                    syn::Error::new_spanned(
+                        &variant.ident,
+                        format!("Variant {} is a second fallback, an enum can only have one", &variant.ident),
+                    ),
+                );
+            }
+            fallback = Some(variant);
+            continue;
+        }
+
+        let (code, category) = match get_code_from_variant(variant) {
+            Ok(Some(code)) => code,
+            Ok(None) => {
+                push_error(
+                    &mut errors,
+                    syn::Error::new_spanned(
+                        &variant.ident,
+                        format!("Variant {} needs a `#[code(..)]` attribute or has to be the `#[fallback]`", &variant.ident),
+                    ),
+                );
+                continue;
+            }
+            Err(error) => {
+                push_error(&mut errors, error);
+                continue;
+            }
+        };
+
+        if let Err(error) = check_coded_variant(variant) {
+            push_error(&mut errors, error);
+        }
+        if let Some((other, ..)) = coded.iter().find(|(_, other_code, _)| *other_code == code) {
+            push_error(
+                &mut errors,
+                syn::Error::new_spanned(
+                    get_attribute_with_name_from_variant(variant, "code").unwrap(),
+                    format!("Code {} of Variant {} is already used by Variant {}", code, &variant.ident, &other.ident),
+                ),
+            );
+        }
+        coded.push((variant, code, category));
+    }
+
+    if let Some(errors) = errors {
+        return Err(errors);
+    }
+
+    match fallback {
+        Some(fallback) => Ok((coded, fallback)),
+        None => Err(syn::Error::new_spanned(
+            enum_name,
+            "`ErrorCode` needs a `#[fallback]` variant for unknown codes",
+        )),
+    }
+}
+
+// Generates the dispatch from a code to its variant. The message is parsed
+// into the field of the variant, if that fails the fallback keeps it.
+pub(crate) fn derive_error_code(input: &syn::DeriveInput) -> syn::Result<TokenStream> {
+    let enum_name = &input.ident;
+    let data_enum = match &input.data {
+        Data::Enum(data_enum) => data_enum,
+        Data::Struct(data) => return Err(not_an_enum(data.struct_token.span)),
+        Data::Union(data) => return Err(not_an_enum(data.union_token.span)),
+    };
+
+    let (coded, fallback) = check_variants(enum_name, data_enum)?;
+    let fallback = &fallback.ident;
+
+    // The remediation and the retry hint of a code, the variant of a parsed message can override them
+    let mut errors: Option<syn::Error> = None;
+    let mut code_remediations = Vec::new();
+    let mut retry_hints = Vec::new();
+    for (variant, ..) in &coded {
+        match remediation::parse_remediation(variant) {
+            Ok(remediation) => code_remediations.push(remediation),
+            Err(error) => push_error(&mut errors, error),
+        }
+        match retry::parse_retry(&variant.attrs) {
+            Ok(hint) => retry_hints.push(hint),
+            Err(error) => push_error(&mut errors, error),
+        }
+    }
+    if let Some(errors) = errors {
+        return Err(errors);
+    }
+
+    let infos = coded
+        .iter()
+        .zip(&code_remediations)
+        .zip(&retry_hints)
+        .map(|(((variant, code, category), remediation), retry)| gen_code_info(variant, *code, category, remediation, retry));
+    let arms = coded
+        .iter()
+        .map(|(variant, code, _)| gen_from_code_arm(enum_name, variant, *code, false));
+    let versioned_arms = coded
+        .iter()
+        .map(|(variant, code, _)| gen_from_code_arm(enum_name, variant, *code, true));
+    let codes = coded.iter().map(|(variant, code, _)| {
+        let variant_name = &variant.ident;
+        match &variant.fields {
+            Fields::Unit => quote! { #enum_name::#variant_name => #code, },
+            _ => quote! { #enum_name::#variant_name(..) => #code, },
+        }
+    });
+
+    let is_fallback = coded.iter().map(|(variant, ..)| {
+        let variant_name = &variant.ident;
+        match &variant.fields {
+            Fields::Unit => quote! { #enum_name::#variant_name => false, },
+            _ => quote! { #enum_name::#variant_name(error) => error.is_fallback(), },
+        }
+    });
+
+    let names = coded.iter().map(|(variant, ..)| {
+        let variant_name = &variant.ident;
+        let name = variant_name.to_string();
+        quote! { #enum_name::#variant_name { .. } => #name, }
+    });
+    let fallback_name = fallback.to_string();
+
+    // The variant and the fields of the parsed message
+    let messages = coded.iter().map(|(variant, ..)| {
+        let variant_name = &variant.ident;
+        match &variant.fields {
+            Fields::Unit => quote! { #enum_name::#variant_name => (None, Vec::new()), },
+            _ => quote! { #enum_name::#variant_name(error) => (Some(error.name()), error.fields()), },
+        }
+    });
+
+    let categories = coded.iter().map(|(variant, _, category)| {
+        let variant_name = &variant.ident;
+        quote! { #enum_name::#variant_name { .. } => ::bitcoin_rpc_errors::ErrorCategory::#category, }
+    });
+
+    let remediations = coded.iter().zip(&code_remediations).map(|((variant, ..), remediation)| {
+        let variant_name = &variant.ident;
+        let remediation = match remediation {
+            Some(remediation) => quote! { Some(#remediation) },
+            None => quote! { None },
+        };
+        match &variant.fields {
+            Fields::Unit => quote! { #enum_name::#variant_name => #remediation, },
+            _ => quote! { #enum_name::#variant_name(error) => error.remediation().or(#remediation), },
+        }
+    });
+
+    let retries = coded.iter().zip(&retry_hints).map(|((variant, ..), retry)| {
+        let variant_name = &variant.ident;
+        let retry = match retry {
+            Some(hint) => hint.clone(),
+            None => quote! { ::bitcoin_rpc_errors::RetryHint::PERMANENT },
+        };
+        match &variant.fields {
+            Fields::Unit => quote! { #enum_name::#variant_name => #retry, },
+            _ => quote! { #enum_name::#variant_name(error) => error.retry_hint().unwrap_or(#retry), },
+        }
+    });
+
+    Ok(quote! {
+        impl #enum_name {
+            pub const VARIANTS: &'static [::bitcoin_rpc_errors::CodeInfo] = &[#( #infos )*];
+
+            pub fn code(&self) -> i32 {
+                match self {
+                    #( #codes )*
+                    #enum_name::#fallback(code, _) => *code,
+                }
+            }
+
+            // The name of the code in protocol.h
+            pub fn name(&self) -> &'static str {
+                match self {
+                    #( #names )*
+                    #enum_name::#fallback(..) => #fallback_name,
+                }
+            }
+
+            // The group of the code in protocol.h, unknown codes have none
+            pub fn category(&self) -> ::bitcoin_rpc_errors::ErrorCategory {
+                match self {
+                    #( #categories )*
+                    #enum_name::#fallback(..) => ::bitcoin_rpc_errors::ErrorCategory::Unknown,
+                }
+            }
+
+            // The variant the message was parsed into and its captures
+            pub fn message_variant(&self) -> (Option<&'static str>, Vec<&dyn std::fmt::Debug>) {
+                match self {
+                    #( #messages )*
+                    #enum_name::#fallback(code, message) => (None, vec![code as &dyn std::fmt::Debug, message]),
+                }
+            }
+
+            // The remediation of the variant the message was parsed into, else the one of the code
+            pub fn remediation(&self) -> Option<::bitcoin_rpc_errors::Remediation> {
+                match self {
+                    #( #remediations )*
+                    #enum_name::#fallback(..) => None,
+                }
+            }
+
+            // Whether and when to retry, the variant of the parsed message overrides
+            // the code and errors without a hint are permanent
+            pub fn retry_hint(&self) -> ::bitcoin_rpc_errors::RetryHint {
+                match self {
+                    #( #retries )*
+                    #enum_name::#fallback(..) => ::bitcoin_rpc_errors::RetryHint::PERMANENT,
+                }
+            }
+
+            pub fn retry_class(&self) -> ::bitcoin_rpc_errors::RetryClass {
+                self.retry_hint().class
+            }
+
+            // The code is unknown or its message matched no pattern
+            pub fn is_fallback(&self) -> bool {
+                match self {
+                    #( #is_fallback )*
+                    #enum_name::#fallback(..) => true,
+                }
+            }
+
+            pub fn from_code(code: i32, message: &str) -> Self {
+                match code {
+                    #( #arms )*
+                    _ => #enum_name::#fallback(code, message.to_string()),
+                }
+            }
+
+            pub fn from_code_for_version(code: i32, message: &str, version: ::bitcoin_rpc_errors::CoreVersion) -> Self {
+                match code {
+                    #( #versioned_arms )*
+                    _ => #enum_name::#fallback(code, message.to_string()),
+                }
+            }
+        }
+    })
+}
@@ -1,198 +1,823 @@
-use proc_macro2::{TokenStream, TokenTree, Ident};
+use proc_macro2::{Ident, Span, TokenStream};
 use regex::Regex;
-use syn::{Attribute, Data::Enum, DataEnum, Fields, Type::Path, Lit, Meta, Variant};
-use quote::{quote, ToTokens};
+use regex_syntax::hir::{Hir, HirKind};
+use syn::{
+    ext::IdentExt, parse::{Parse, ParseStream}, punctuated::Punctuated, spanned::Spanned, Attribute, Data, DataEnum, Fields, LitStr, PathArguments,
+    Token, Type, Type::Path, Variant,
+};
+use quote::{format_ident, quote, quote_spanned};
 
-fn get_string_tokens_from_attribute(attribute: &Attribute) -> Vec<String> {
-    let mut string_tokens = Vec::new();
+mod error_code;
+mod remediation;
+mod retry;
+mod shadowing;
 
-    if let Meta::List(list) = &attribute.meta {
-        for token_trees in list.to_token_stream() {
-            if let TokenTree::Group(group) = token_trees {
-                for token_trees2 in group.stream() {
-                    if let TokenTree::Literal(literal) = token_trees2 {
-                        if let Lit::Str(pattern) = Lit::new(literal) {
-                            string_tokens.push(pattern.value())
-                        }
-                    }
-                }
+// A pattern of the `patterns` attribute together with its literal, so that
+// errors can point at the offending string
+struct Pattern {
+    literal: LitStr,
+    regex: Regex,
+    versions: Versions,
+}
+
+// The Core releases a pattern is valid for, `since` is inclusive and `until` exclusive
+#[derive(Clone, Copy, Default)]
+struct Versions {
+    since: Option<(u32, u32)>,
+    until: Option<(u32, u32)>,
+}
+
+impl Versions {
+    fn overlaps(&self, other: &Versions) -> bool {
+        let starts_before = |since: Option<(u32, u32)>, until: Option<(u32, u32)>| match (since, until) {
+            (Some(since), Some(until)) => since < until,
+            _ => true,
+        };
+
+        starts_before(self.since, other.until) && starts_before(other.since, self.until)
+    }
+}
+
+// An argument of the `patterns` attribute, either a pattern or a version like `since = "24.0"`
+enum PatternArg {
+    Pattern(LitStr),
+    Version(Ident, LitStr),
+}
+
+impl Parse for PatternArg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(syn::Ident) && input.peek2(Token![=]) {
+            let key: Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+            return Ok(PatternArg::Version(key, input.parse()?));
+        }
+
+        Ok(PatternArg::Pattern(input.parse()?))
+    }
+}
+
+fn parse_version(literal: &LitStr) -> syn::Result<(u32, u32)> {
+    let value = literal.value();
+    value
+        .split_once('.')
+        .and_then(|(major, minor)| Some((major.parse().ok()?, minor.parse().ok()?)))
+        .ok_or_else(|| syn::Error::new(literal.span(), format!("Invalid Core version `{}`, expected e.g. \"25.0\"", value)))
+}
+
+fn get_string_tokens_from_attribute(attribute: &Attribute) -> syn::Result<(Vec<LitStr>, Versions)> {
+    let args = attribute.parse_args_with(Punctuated::<PatternArg, Token![,]>::parse_terminated)?;
+
+    let mut string_tokens = Vec::new();
+    let mut versions = Versions::default();
+    for arg in args {
+        match arg {
+            PatternArg::Pattern(literal) => string_tokens.push(literal),
+            PatternArg::Version(key, literal) if key == "since" => versions.since = Some(parse_version(&literal)?),
+            PatternArg::Version(key, literal) if key == "until" => versions.until = Some(parse_version(&literal)?),
+            PatternArg::Version(key, _) => {
+                return Err(syn::Error::new_spanned(&key, format!("Unknown argument `{}`, expected `since` or `until`", key)))
             }
         }
     }
 
-    string_tokens
+    if string_tokens.is_empty() {
+        return Err(syn::Error::new_spanned(attribute, "`patterns` needs at least one regex pattern"));
+    }
+    if let (Some(since), Some(until)) = (versions.since, versions.until) {
+        if since >= until {
+            return Err(syn::Error::new_spanned(attribute, "`since` has to be an earlier version than `until`"));
+        }
+    }
+
+    Ok((string_tokens, versions))
 }
 
 fn get_attribute_with_name_from_variant<'a>(
     variant: &'a Variant,
     name: &str,
 ) -> Option<&'a Attribute> {
-    for attr in &variant.attrs {
-        if attr.path().is_ident(name) {
-            return Some(attr);
+    variant.attrs.iter().find(|attr| attr.path().is_ident(name))
+}
+
+fn get_patterns_from_variant(variant: &Variant) -> syn::Result<Vec<Pattern>> {
+    // A variant may have several `patterns` attributes, e.g. for different Core versions
+    let attrs: Vec<&Attribute> = variant.attrs.iter().filter(|attr| attr.path().is_ident("patterns")).collect();
+    if attrs.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &variant.ident,
+            format!("Variant {} needs a `#[patterns(..)]` attribute or has to be the `#[fallback]`", &variant.ident),
+        ));
+    }
+
+    let mut errors: Option<syn::Error> = None;
+    let mut patterns = Vec::new();
+    for attr in attrs {
+        let (literals, versions) = get_string_tokens_from_attribute(attr)?;
+        for literal in literals {
+            match Regex::new(&literal.value()) {
+                Ok(regex) => patterns.push(Pattern { literal, regex, versions }),
+                Err(err) => push_error(
+                    &mut errors,
+                    syn::Error::new(literal.span(), format!("Invalid regex pattern\n{}", err)),
+                ),
+            }
         }
     }
 
-    None
+    match errors {
+        Some(errors) => Err(errors),
+        None => Ok(patterns),
+    }
 }
 
-fn get_string_tokens_with_name_from_variant(variant: &Variant, name: &str) -> Option<Vec<String>> {
-    match get_attribute_with_name_from_variant(variant, name) {
-        Some(attr) => {
-            let patterns = get_string_tokens_from_attribute(attr);
-            if patterns.is_empty() {
-                None
-            } else {
-                Some(patterns)
-            }
-        }
-        None => None,
+fn push_error(errors: &mut Option<syn::Error>, error: syn::Error) {
+    match errors {
+        Some(errors) => errors.combine(error),
+        None => *errors = Some(error),
     }
 }
 
-fn get_number_unnamed_fields_from_variant(variant: &Variant) -> Option<usize> {
+fn get_field_names_from_variant(variant: &Variant) -> Option<Vec<String>> {
     match &variant.fields {
-        Fields::Named(_) => None,
-        _ => Some(variant.fields.len()),
+        Fields::Named(fields) => Some(
+            fields
+                .named
+                .iter()
+                .filter_map(|field| field.ident.as_ref())
+                .map(|ident| ident.unraw().to_string())
+                .collect(),
+        ),
+        _ => None,
     }
 }
 
-fn regexs_same_num_captures(regexs: &[Regex]) -> Option<usize> {
-    let lens: Vec<usize> = regexs
-        .iter()
-        .map(|regex| regex.captures_len() - 1)
-        .collect();
+// Every pattern of a tuple variant needs one capture per field
+fn check_num_captures(variant: &Variant, patterns: &[Pattern]) -> syn::Result<()> {
+    for pattern in patterns {
+        let num_captures = pattern.regex.captures_len() - 1;
+        if num_captures != variant.fields.len() {
+            return Err(syn::Error::new(
+                pattern.literal.span(),
+                format!(
+                    "The number of captures in pattern does not match with the number of fields in Variant {}! {} != {}",
+                    &variant.ident,
+                    num_captures,
+                    variant.fields.len()
+                ),
+            ));
+        }
+    }
 
-    if lens.windows(2).all(|w| w[0] == w[1]) {
-        Some(lens[0])
-    } else {
-        None
+    Ok(())
+}
+
+// Every capture group of a struct variant has to be named after one of its fields
+fn check_named_captures(variant: &Variant, field_names: &[String], patterns: &[Pattern]) -> syn::Result<()> {
+    let mut expected: Vec<&str> = field_names.iter().map(|name| name.as_str()).collect();
+    expected.sort_unstable();
+
+    for pattern in patterns {
+        let regex = &pattern.regex;
+        let mut names: Vec<&str> = regex.capture_names().flatten().collect();
+        if names.len() != regex.captures_len() - 1 {
+            return Err(syn::Error::new(
+                pattern.literal.span(),
+                format!("All capture groups of pattern have to be named after the fields of Variant {}!", &variant.ident),
+            ));
+        }
+
+        names.sort_unstable();
+        if names != expected {
+            return Err(syn::Error::new(
+                pattern.literal.span(),
+                format!(
+                    "The named groups of pattern do not match with the fields of Variant {}! {:?} != {:?}",
+                    &variant.ident, names, expected
+                ),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn is_fallback_variant(variant: &Variant) -> bool {
+    get_attribute_with_name_from_variant(variant, "fallback").is_some()
+}
+
+// The fallback variant takes every message no pattern matched. It has no
+// patterns and a single field which can be built `From<String>`.
+fn check_fallback_variant(variant: &Variant) -> syn::Result<()> {
+    let attr = get_attribute_with_name_from_variant(variant, "fallback").unwrap();
+    attr.meta.require_path_only()?;
+
+    if let Some(patterns) = get_attribute_with_name_from_variant(variant, "patterns") {
+        return Err(syn::Error::new_spanned(
+            patterns,
+            format!("The fallback Variant {} can not have patterns", &variant.ident),
+        ));
     }
+
+    if variant.fields.len() != 1 {
+        return Err(syn::Error::new_spanned(
+            &variant.ident,
+            format!("The fallback Variant {} needs exactly one field for the message", &variant.ident),
+        ));
+    }
+
+    Ok(())
 }
 
-// Checks if the enum contains a variant "Generic(String)"
-fn contains_generic_variant(data_enum: &DataEnum) -> bool {
-    data_enum.variants.iter().any(|variant| is_generic_string_variant(variant))
+// Returns the fallback variant of the enum, at most one variant may be marked
+fn get_fallback_variant(data_enum: &DataEnum) -> syn::Result<Option<&Variant>> {
+    let mut fallbacks = data_enum.variants.iter().filter(|variant| is_fallback_variant(variant));
+    let fallback = fallbacks.next();
+    if let Some(fallback) = fallback {
+        check_fallback_variant(fallback)?;
+    }
+
+    let mut errors: Option<syn::Error> = None;
+    for variant in fallbacks {
+        push_error(
+            &mut errors,
+            syn::Error::new_spanned(
+                get_attribute_with_name_from_variant(variant, "fallback").unwrap(),
+                format!("Variant {} is a second fallback, an enum can only have one", &variant.ident),
+            ),
+        );
+    }
+
+    match errors {
+        Some(errors) => Err(errors),
+        None => Ok(fallback),
+    }
 }
 
-fn is_generic_string_variant(variant: &syn::Variant) -> bool {
-    if variant.ident != "Generic" {
-        return false;
+fn gen_fallback(enum_name: &Ident, variant: &Variant) -> TokenStream {
+    let variant_name = &variant.ident;
+    let field = variant.fields.iter().next().unwrap();
+    let ty = &field.ty;
+    // Point at the field if its type can not be built from the message
+    let message = quote_spanned! {ty.span()=>
+        <#ty as ::std::convert::From<String>>::from(s.to_string())
+    };
+
+    let construct = match &field.ident {
+        Some(field) => quote! { #enum_name::#variant_name { #field: message } },
+        None => quote! { #enum_name::#variant_name(message) },
+    };
+
+    // The conversion is spanned to the field, a `String` field would otherwise
+    // be reported as useless conversion in the user's code
+    quote! {
+        #[allow(clippy::useless_conversion)]
+        let message = #message;
+        Ok(#construct)
     }
-    
-    if let Fields::Unnamed(fields) = &variant.fields {
-        if fields.unnamed.len() == 1 {
-            if let Path(type_path) = &fields.unnamed[0].ty {
-                if let Some(segment) = type_path.path.segments.last() {
-                    return segment.ident == "String";
+}
+
+// Enums without a fallback get their own error type which keeps the message
+// that could not be parsed
+fn gen_parse_error(input: &syn::DeriveInput, error_name: &Ident) -> TokenStream {
+    let enum_name = &input.ident;
+    let vis = &input.vis;
+    let doc = format!("The message did not match any pattern of [`{}`]", enum_name);
+    let display = format!("unknown {} message: {{}}", enum_name);
+
+    quote! {
+        #[doc = #doc]
+        #[derive(Clone, Debug, PartialEq, Eq)]
+        #vis struct #error_name(String);
+
+        impl #error_name {
+            pub fn message(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl std::fmt::Display for #error_name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, #display, self.0)
+            }
+        }
+
+        impl std::error::Error for #error_name {}
+    }
+}
+
+fn is_option_type(ty: &Type) -> bool {
+    if let Path(type_path) = ty {
+        if let Some(segment) = type_path.path.segments.last() {
+            return segment.ident == "Option"
+                && matches!(segment.arguments, PathArguments::AngleBracketed(_));
+        }
+    }
+
+    false
+}
+
+// Collects the indices of the groups that do not participate in every match,
+// e.g. groups inside of `?`, `*` or an alternation
+fn collect_optional_groups(hir: &Hir, optional: bool, groups: &mut Vec<usize>) {
+    match hir.kind() {
+        HirKind::Capture(capture) => {
+            if optional {
+                groups.push(capture.index as usize);
+            }
+            collect_optional_groups(&capture.sub, optional, groups);
+        }
+        HirKind::Repetition(repetition) => {
+            collect_optional_groups(&repetition.sub, optional || repetition.min == 0, groups);
+        }
+        HirKind::Concat(hirs) => {
+            for hir in hirs {
+                collect_optional_groups(hir, optional, groups);
+            }
+        }
+        HirKind::Alternation(hirs) => {
+            for hir in hirs {
+                collect_optional_groups(hir, true, groups);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn get_optional_groups(pattern: &str) -> Vec<usize> {
+    let mut groups = Vec::new();
+    if let Ok(hir) = regex_syntax::parse(pattern) {
+        collect_optional_groups(&hir, false, &mut groups);
+    }
+
+    groups
+}
+
+// A group that does not participate in every match has to be bound to an `Option` field
+fn check_optional_captures(variant: &Variant, patterns: &[Pattern]) -> syn::Result<()> {
+    for pattern in patterns {
+        let regex = &pattern.regex;
+        for group in get_optional_groups(regex.as_str()) {
+            let field = match &variant.fields {
+                Fields::Named(fields) => regex
+                    .capture_names()
+                    .nth(group)
+                    .flatten()
+                    .and_then(|name| {
+                        fields.named.iter().find(|field| {
+                            field.ident.as_ref().is_some_and(|ident| ident.unraw() == name)
+                        })
+                    }),
+                fields => fields.iter().nth(group - 1),
+            };
+
+            if let Some(field) = field {
+                if !is_option_type(&field.ty) {
+                    let mut error = syn::Error::new(
+                        pattern.literal.span(),
+                        format!("Group {} of pattern is optional and has to be bound to an Option field in Variant {}!", group, &variant.ident),
+                    );
+                    error.combine(syn::Error::new_spanned(&field.ty, "this field is not an Option"));
+                    return Err(error);
                 }
             }
         }
     }
 
+    Ok(())
+}
+
+fn is_string_type(ty: &Type) -> bool {
+    if let Path(type_path) = ty {
+        if let Some(segment) = type_path.path.segments.last() {
+            return segment.ident == "String";
+        }
+    }
+
     false
 }
 
-fn gen_variant_check(enum_name: &Ident, variant_name: &Ident, num_captures: usize, patterns: &[String]) 
-    -> Result<TokenStream, ()>
-{
-    let mut output = quote! {
-        if let Some(v) = vec![#( #patterns ),*]
+// A pattern that is repeated by a later variant can never match there, if the
+// captures of the first variant always convert. Neither can the messages of a
+// later variant that a broader pattern declared before it swallows, e.g.
+// "^Missing (.*)" before "^Missing amount for (.*)".
+fn check_unreachable_patterns(variants: &[(&Variant, Vec<Pattern>)]) -> syn::Result<()> {
+    let mut errors: Option<syn::Error> = None;
+    for (i, (first, first_patterns)) in variants.iter().enumerate() {
+        if !first.fields.iter().all(|field| is_string_type(&field.ty)) {
+            continue;
+        }
+
+        for (later, later_patterns) in &variants[i + 1..] {
+            // Only patterns of the same Core versions compete for a message
+            let overlapping = |pattern: &Pattern| {
+                first_patterns.iter().any(|other| other.versions.overlaps(&pattern.versions))
+            };
+            let first_regexs: Vec<Regex> = first_patterns.iter().map(|pattern| pattern.regex.clone()).collect();
+            let later_regexs: Vec<Regex> = later_patterns
+                .iter()
+                .filter(|pattern| overlapping(pattern))
+                .map(|pattern| pattern.regex.clone())
+                .collect();
+            if let (Some(sample), Some(pattern)) = (shadowing::swallowed_sample(&later_regexs, &first_regexs), later_patterns.first()) {
+                push_error(
+                    &mut errors,
+                    syn::Error::new(
+                        pattern.literal.span(),
+                        format!(
+                            "Messages of Variant {} like {:?} are already matched by Variant {}, declare {} first",
+                            &later.ident, sample, &first.ident, &later.ident
+                        ),
+                    ),
+                );
+                continue;
+            }
+
+            for pattern in later_patterns {
+                if first_patterns.iter().any(|other| {
+                    other.regex.as_str() == pattern.regex.as_str() && other.versions.overlaps(&pattern.versions)
+                }) {
+                    push_error(
+                        &mut errors,
+                        syn::Error::new(
+                            pattern.literal.span(),
+                            format!("Pattern of Variant {} is already matched by Variant {} and can never match!", &later.ident, &first.ident),
+                        ),
+                    );
+                }
+            }
+        }
+    }
+
+    match errors {
+        Some(errors) => Err(errors),
+        None => Ok(()),
+    }
+}
+
+fn check_variant(variant: &Variant) -> syn::Result<Vec<Pattern>> {
+    let patterns = get_patterns_from_variant(variant)?;
+
+    match get_field_names_from_variant(variant) {
+        Some(field_names) => check_named_captures(variant, &field_names, &patterns)?,
+        None => check_num_captures(variant, &patterns)?,
+    }
+    check_optional_captures(variant, &patterns)?;
+
+    Ok(patterns)
+}
+
+// Optional groups become `None` if they do not participate in the match
+fn gen_capture_conversion(lookup: TokenStream, ty: &Type) -> TokenStream {
+    if is_option_type(ty) {
+        quote! { #lookup.map(|capture| capture.as_str().parse()).transpose() }
+    } else {
+        quote! { #lookup.unwrap().as_str().parse() }
+    }
+}
+
+// Every capture is converted with `FromStr` into the type of its field. If a
+// conversion fails the pattern does not match and the next one is tried.
+// Struct variants look up their captures by the group named after the field.
+// `indices` are the positions of the variant's patterns in the `RegexSet`.
+fn gen_variant_check(enum_name: &Ident, variant: &Variant, indices: &[usize]) -> TokenStream {
+    let variant_name = &variant.ident;
+
+    let (bindings, lookups, construct): (Vec<Ident>, Vec<TokenStream>, TokenStream) = match &variant.fields {
+        Fields::Unit => (Vec::new(), Vec::new(), quote! { #enum_name::#variant_name }),
+        Fields::Unnamed(fields) => {
+            let bindings: Vec<Ident> = (0..fields.unnamed.len())
+                .map(|i| format_ident!("capture_{}", i))
+                .collect();
+            let lookups = fields
+                .unnamed
+                .iter()
+                .enumerate()
+                .map(|(i, field)| {
+                    let index = i + 1;
+                    gen_capture_conversion(quote! { captures.get(#index) }, &field.ty)
+                })
+                .collect();
+            let construct = quote! { #enum_name::#variant_name(#( #bindings ),*) };
+            (bindings, lookups, construct)
+        }
+        Fields::Named(fields) => {
+            let bindings: Vec<Ident> = fields
+                .named
+                .iter()
+                .filter_map(|field| field.ident.clone())
+                .collect();
+            let lookups = fields
+                .named
+                .iter()
+                .filter_map(|field| Some((field.ident.as_ref()?, &field.ty)))
+                .map(|(ident, ty)| {
+                    let name = ident.unraw().to_string();
+                    gen_capture_conversion(quote! { captures.name(#name) }, ty)
+                })
+                .collect();
+            let construct = quote! { #enum_name::#variant_name { #( #bindings ),* } };
+            (bindings, lookups, construct)
+        }
+    };
+
+    quote! {
+        for index in [#( #indices ),*] {
+            if !matches.matched(index) || !valid[index] {
+                continue;
+            }
+            if let Some(captures) = regexes[index].captures(s) {
+                if let (#( Ok(#bindings), )*) = (#( #lookups, )*) {
+                    return Ok(#construct);
+                }
+            }
+        }
+    }
+}
+
+fn gen_version(version: Option<(u32, u32)>) -> TokenStream {
+    match version {
+        Some((major, minor)) => quote! { Some(::bitcoin_rpc_errors::CoreVersion::new(#major, #minor)) },
+        None => quote! { None },
+    }
+}
+
+// Patterns without versions are valid for every Core version. Without a
+// version every pattern is tried.
+fn gen_version_check(versions: Versions) -> TokenStream {
+    if versions.since.is_none() && versions.until.is_none() {
+        return quote! { true };
+    }
+
+    let since = gen_version(versions.since);
+    let until = gen_version(versions.until);
+    quote! { version.is_none_or(|version| version.is_within(#since, #until)) }
+}
+
+// The patterns are compiled once on the first call. The `RegexSet` finds all
+// matching patterns in a single pass, only those are captured afterwards.
+fn gen_pattern_matching(variants: &[(&Variant, Vec<Pattern>)], enum_name: &Ident) -> TokenStream {
+    let mut patterns = Vec::new();
+    let mut version_checks = Vec::new();
+    let mut checks = Vec::new();
+    // The variants are checked in declaration order
+    for (variant, variant_patterns) in variants {
+        let indices: Vec<usize> = (patterns.len()..patterns.len() + variant_patterns.len()).collect();
+        patterns.extend(variant_patterns.iter().map(|pattern| &pattern.literal));
+        version_checks.extend(variant_patterns.iter().map(|pattern| gen_version_check(pattern.versions)));
+        checks.push(gen_variant_check(enum_name, variant, &indices));
+    }
+
+    if patterns.is_empty() {
+        return TokenStream::new();
+    }
+
+    let num_patterns = patterns.len();
+    quote! {
+        static REGEXES: std::sync::OnceLock<(::bitcoin_rpc_errors::__private::regex::RegexSet, Vec<::bitcoin_rpc_errors::__private::regex::Regex>)> = std::sync::OnceLock::new();
+        let (set, regexes) = REGEXES.get_or_init(|| {
+            // The patterns were already checked when the enum was compiled
+            let patterns: [&str; #num_patterns] = [#( #patterns ),*];
+            (
+                ::bitcoin_rpc_errors::__private::regex::RegexSet::new(patterns).unwrap(),
+                patterns.iter().map(|pattern| ::bitcoin_rpc_errors::__private::regex::Regex::new(pattern).unwrap()).collect(),
+            )
+        });
+        let matches = set.matches(s);
+        let valid: [bool; #num_patterns] = [#( #version_checks ),*];
+
+        #( #checks )*
+    }
+}
+
+// Lists the variants in declaration order together with their patterns, the
+// fallback has none
+fn gen_variant_infos(
+    data_enum: &DataEnum,
+    variants: &[(&Variant, Vec<Pattern>)],
+    remediations: &[TokenStream],
+    retry_hints: &[TokenStream],
+) -> TokenStream {
+    let infos = data_enum.variants.iter().zip(remediations).zip(retry_hints).map(|((variant, remediation), retry)| {
+        let name = variant.ident.to_string();
+        let patterns: Vec<&LitStr> = variants
             .iter()
-            .find_map(|&pattern| regex::Regex::new(pattern).ok()?.captures(s))
-            .map(|captures| captures.iter().skip(1).map(|capture| capture.unwrap().as_str().to_string()).collect::<Vec<String>>())
-    }.to_string();
-    output += "{";
-        
-    output += &match num_captures {
-        0 => quote! {
-            if let [] = v.as_slice() {
-                return Ok(#enum_name::#variant_name);
-            }
-        },
-        1 => quote! {
-            if let [a] = v.as_slice() {
-                return Ok(#enum_name::#variant_name(a.to_string()));
-            }
-        },
-        2 => quote! {
-            if let [a, b] = v.as_slice() {
-                return Ok(#enum_name::#variant_name(a.to_string(), b.to_string()));
-            }
-        },
-        3 => quote! {
-            if let [a, b, c] = v.as_slice() {
-                return Ok(#enum_name::#variant_name(a.to_string(), b.to_string(), c.to_string()));
-            }
-        },
-        4 => quote! {
-            if let [a, b, c, d] = v.as_slice() {
-                return Ok(#enum_name::#variant_name(a.to_string(), b.to_string(), c.to_string(), d.to_string()));
-            }
-        },
-        _ => return Err(()),
-    }.to_string();
-    output += "}";
-
-    Ok(output.parse::<TokenStream>().unwrap())
-}
-
-
-#[proc_macro_derive(EnumError, attributes(patterns))]
-pub fn derive_from_str_from_patterns(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    // Parse the input tokens
-    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+            .find(|(other, _)| other.ident == variant.ident)
+            .map(|(_, patterns)| patterns.iter().map(|pattern| &pattern.literal).collect())
+            .unwrap_or_default();
+        let captures = if is_fallback_variant(variant) { 0 } else { variant.fields.len() };
+        let fallback = is_fallback_variant(variant);
+
+        quote! {
+            ::bitcoin_rpc_errors::VariantInfo {
+                name: #name,
+                patterns: &[#( #patterns ),*],
+                captures: #captures,
+                fallback: #fallback,
+                remediation: #remediation,
+                retry: #retry,
+            }
+        }
+    });
+
+    quote! { &[#( #infos ),*] }
+}
+
+// Matches every variant to its value, e.g. its remediation
+fn gen_variant_match(enum_name: &Ident, data_enum: &DataEnum, values: &[TokenStream]) -> TokenStream {
+    if data_enum.variants.is_empty() {
+        return quote! { match *self {} };
+    }
 
-    // Check if the input is an enum
-    match input.data {
-        Enum(e) => {
-            let mut output: String = format!("
-                impl std::str::FromStr for {} {{
-                    type Err = ();
-                    
-                    fn from_str(s: &str) -> Result<Self, Self::Err> {{
-            ", &input.ident);
-
-            for v in e.variants.iter().filter(|variant| !is_generic_string_variant(variant)) {
-                let patterns: Vec<String> = get_string_tokens_with_name_from_variant(v, "patterns")
-                    .expect("No Attribute \"patterns\" found!");
-                let regexs: Vec<regex::Regex> = patterns
-                    .iter()
-                    .map(|pattern| {
-                        regex::Regex::new(pattern)
-                            .unwrap_or_else(|_| panic!("Invalid regex pattern: {}", pattern))
-                    })
-                    .collect();
-
-                let num_unnamed_fields = get_number_unnamed_fields_from_variant(v)
-                    .expect("Enum Variant has to have none or unnamed fields");
-
-                if let Some(x) = regexs_same_num_captures(&regexs) {
-                    if num_unnamed_fields != x {
-                        panic!("The number of captures in patterns does not match with the number of fields in Variant {}! {} != {}", &v.ident, num_unnamed_fields, x);
+    let arms = data_enum.variants.iter().zip(values).map(|(variant, value)| {
+        let variant_name = &variant.ident;
+        quote! { #enum_name::#variant_name { .. } => #value, }
+    });
+
+    quote! { match self { #( #arms )* } }
+}
+
+// The name of the variant and its fields, e.g. to explain a parsed error
+fn gen_name_and_fields(enum_name: &Ident, data_enum: &DataEnum) -> (TokenStream, TokenStream) {
+    if data_enum.variants.is_empty() {
+        return (quote! { match *self {} }, quote! { match *self {} });
+    }
+
+    let (names, fields): (Vec<_>, Vec<_>) = data_enum
+        .variants
+        .iter()
+        .map(|variant| {
+            let variant_name = &variant.ident;
+            let name = variant_name.to_string();
+            let bindings: Vec<Ident> = (0..variant.fields.len()).map(|i| format_ident!("field{}", i)).collect();
+            let pattern = match &variant.fields {
+                Fields::Unit => quote! { #enum_name::#variant_name },
+                Fields::Unnamed(_) => quote! { #enum_name::#variant_name( #( #bindings ),* ) },
+                Fields::Named(fields) => {
+                    let idents = fields.named.iter().map(|field| &field.ident);
+                    quote! { #enum_name::#variant_name { #( #idents: #bindings ),* } }
+                }
+            };
+
+            (
+                quote! { #enum_name::#variant_name { .. } => #name, },
+                quote! { #pattern => vec![#( #bindings as &dyn std::fmt::Debug ),*], },
+            )
+        })
+        .unzip();
+
+    (quote! { match self { #( #names )* } }, quote! { match self { #( #fields )* } })
+}
+
+fn derive_enum_error(input: &syn::DeriveInput) -> syn::Result<TokenStream> {
+    let enum_name = &input.ident;
+    let data_enum = match &input.data {
+        Data::Enum(data_enum) => data_enum,
+        Data::Struct(data) => return Err(not_an_enum(data.struct_token.span)),
+        Data::Union(data) => return Err(not_an_enum(data.union_token.span)),
+    };
+
+    // Report the errors of all variants instead of stopping at the first one
+    let mut errors: Option<syn::Error> = None;
+    let fallback = get_fallback_variant(data_enum).unwrap_or_else(|error| {
+        push_error(&mut errors, error);
+        None
+    });
+    let mut variants = Vec::new();
+    for variant in data_enum.variants.iter().filter(|variant| !is_fallback_variant(variant)) {
+        match check_variant(variant) {
+            Ok(patterns) => variants.push((variant, patterns)),
+            Err(error) => push_error(&mut errors, error),
+        }
+    }
+    if let Err(error) = check_unreachable_patterns(&variants) {
+        push_error(&mut errors, error);
+    }
+    let remediations = remediation::get_remediations(data_enum).unwrap_or_else(|error| {
+        push_error(&mut errors, error);
+        Vec::new()
+    });
+    let retry_hints = retry::get_retry_hints(data_enum).unwrap_or_else(|error| {
+        push_error(&mut errors, error);
+        Vec::new()
+    });
+    if let Some(errors) = errors {
+        return Err(errors);
+    }
+
+    let pattern_matching = gen_pattern_matching(&variants, enum_name);
+    let variant_infos = gen_variant_infos(data_enum, &variants, &remediations, &retry_hints);
+    let remediation = gen_variant_match(enum_name, data_enum, &remediations);
+    let retry_hint = gen_variant_match(enum_name, data_enum, &retry_hints);
+    let is_fallback = match fallback {
+        Some(variant) => {
+            let variant_name = &variant.ident;
+            quote! { matches!(self, #enum_name::#variant_name { .. }) }
+        }
+        None => quote! { false },
+    };
+
+    let (name, fields) = gen_name_and_fields(enum_name, data_enum);
+
+    let (error_type, parse_error, fallback) = match fallback {
+        Some(variant) => (
+            quote! { std::convert::Infallible },
+            // Lets `ErrorCode` wrap the enum
+            quote! {
+                impl ::bitcoin_rpc_errors::__private::WithFallback for #enum_name {
+                    fn parse_message(s: &str, version: Option<::bitcoin_rpc_errors::CoreVersion>) -> Self {
+                        match Self::parse_with_version(s, version) {
+                            Ok(error) => error,
+                            Err(never) => match never {},
+                        }
                     }
-                } else {
-                    panic!("The number of captures in patterns have to be the same for variant {}!", &v.ident);
                 }
-                
-                output += &gen_variant_check(&input.ident, &v.ident, num_unnamed_fields, &patterns).unwrap().to_string();
+            },
+            gen_fallback(enum_name, variant),
+        ),
+        None => {
+            let error_name = format_ident!("{}ParseError", enum_name);
+            (
+                quote! { #error_name },
+                gen_parse_error(input, &error_name),
+                quote! { Err(#error_name(s.to_string())) },
+            )
+        }
+    };
+
+    Ok(quote! {
+        #parse_error
+
+        impl #enum_name {
+            pub const VARIANTS: &'static [::bitcoin_rpc_errors::VariantInfo] = #variant_infos;
+
+            // The message matched no pattern
+            pub fn is_fallback(&self) -> bool {
+                #is_fallback
             }
-            
-            if contains_generic_variant(&e) {
-                output += &format!("
-                    Ok({}::Generic(s.to_string()))
-                ", &input.ident);
-            } else {
-                output += "
-                    Err(())
-                ";
+
+            pub fn name(&self) -> &'static str {
+                #name
             }
-            output += "}}";
 
-            output.parse::<TokenStream>().unwrap().into()
+            // The captures the message was parsed into
+            pub fn fields(&self) -> Vec<&dyn std::fmt::Debug> {
+                #fields
+            }
+
+            // What an operator can do about the error, see `#[remediation(..)]`
+            pub fn remediation(&self) -> Option<::bitcoin_rpc_errors::Remediation> {
+                #remediation
+            }
+
+            // Whether and when to retry, see `#[retry(..)]`
+            pub fn retry_hint(&self) -> Option<::bitcoin_rpc_errors::RetryHint> {
+                #retry_hint
+            }
+
+            // Only tries the patterns that are valid for the given Core version
+            pub fn parse_for_version(s: &str, version: ::bitcoin_rpc_errors::CoreVersion) -> Result<Self, #error_type> {
+                Self::parse_with_version(s, Some(version))
+            }
+
+            #[allow(unused_variables)]
+            fn parse_with_version(s: &str, version: Option<::bitcoin_rpc_errors::CoreVersion>) -> Result<Self, #error_type> {
+                #pattern_matching
+
+                #fallback
+            }
         }
-        _ => {
-            panic!("`EnumError` can only be derived on enums");
+
+        impl std::str::FromStr for #enum_name {
+            type Err = #error_type;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Self::parse_with_version(s, None)
+            }
         }
-    }
+    })
+}
+
+fn not_an_enum(span: Span) -> syn::Error {
+    syn::Error::new(span, "`EnumError` can only be derived on enums")
+}
+
+// The generated inherent methods like `name` collide with methods of the same name
+// the enum defines itself.
+#[proc_macro_derive(EnumError, attributes(patterns, fallback, remediation, retry))]
+pub fn derive_from_str_from_patterns(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    // Parse the input tokens
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+
+    derive_enum_error(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[proc_macro_derive(ErrorCode, attributes(code, fallback, remediation, retry))]
+pub fn derive_error_code(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+
+    error_code::derive_error_code(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
 }
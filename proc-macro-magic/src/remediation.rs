@@ -0,0 +1,68 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{DataEnum, LitStr, Variant};
+
+// `#[remediation(rpc = "keypoolrefill", flag = "-prune=0", docs = "https://...")]`
+// next to the patterns of a variant or on a code, every key is optional but one is needed
+pub(crate) fn parse_remediation(variant: &Variant) -> syn::Result<Option<TokenStream>> {
+    let attr = match variant.attrs.iter().find(|attr| attr.path().is_ident("remediation")) {
+        Some(attr) => attr,
+        None => return Ok(None),
+    };
+
+    let mut rpc: Option<LitStr> = None;
+    let mut flag: Option<LitStr> = None;
+    let mut docs: Option<LitStr> = None;
+    attr.parse_nested_meta(|meta| {
+        let slot = if meta.path.is_ident("rpc") {
+            &mut rpc
+        } else if meta.path.is_ident("flag") {
+            &mut flag
+        } else if meta.path.is_ident("docs") {
+            &mut docs
+        } else {
+            return Err(meta.error("unknown remediation, expected `rpc`, `flag` or `docs`"));
+        };
+
+        if slot.is_some() {
+            return Err(meta.error("this remediation is already set"));
+        }
+        *slot = Some(meta.value()?.parse()?);
+        Ok(())
+    })?;
+
+    if rpc.is_none() && flag.is_none() && docs.is_none() {
+        return Err(syn::Error::new_spanned(
+            attr,
+            format!("The remediation of Variant {} needs an `rpc`, a `flag` or `docs`", &variant.ident),
+        ));
+    }
+
+    let option = |value: Option<LitStr>| match value {
+        Some(value) => quote! { Some(#value) },
+        None => quote! { None },
+    };
+    let (rpc, flag, docs) = (option(rpc), option(flag), option(docs));
+
+    Ok(Some(quote! {
+        ::bitcoin_rpc_errors::Remediation { rpc: #rpc, flag: #flag, docs: #docs }
+    }))
+}
+
+// The remediation of every variant, `None` for the ones without the attribute
+pub(crate) fn get_remediations(data_enum: &DataEnum) -> syn::Result<Vec<TokenStream>> {
+    let mut errors: Option<syn::Error> = None;
+    let mut remediations = Vec::new();
+    for variant in &data_enum.variants {
+        match parse_remediation(variant) {
+            Ok(Some(remediation)) => remediations.push(quote! { Some(#remediation) }),
+            Ok(None) => remediations.push(quote! { None }),
+            Err(error) => crate::push_error(&mut errors, error),
+        }
+    }
+
+    match errors {
+        Some(errors) => Err(errors),
+        None => Ok(remediations),
+    }
+}
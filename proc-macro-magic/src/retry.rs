@@ -0,0 +1,79 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Attribute, DataEnum, LitInt};
+
+const CLASSES: [(&str, &str); 4] = [
+    ("transient", "Transient"),
+    ("permanent", "Permanent"),
+    ("needs_operator", "NeedsOperator"),
+    ("idempotent_success", "IdempotentSuccess"),
+];
+
+// `#[retry(transient, backoff_ms = 1000)]`, the class is required and only a
+// transient error has a backoff
+pub(crate) fn parse_retry(attrs: &[Attribute]) -> syn::Result<Option<TokenStream>> {
+    let attr = match attrs.iter().find(|attr| attr.path().is_ident("retry")) {
+        Some(attr) => attr,
+        None => return Ok(None),
+    };
+
+    let mut class: Option<&str> = None;
+    let mut backoff_ms: Option<LitInt> = None;
+    attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident("backoff_ms") {
+            if backoff_ms.is_some() {
+                return Err(meta.error("the backoff is already set"));
+            }
+            backoff_ms = Some(meta.value()?.parse()?);
+            return Ok(());
+        }
+
+        let (_, variant) = CLASSES
+            .iter()
+            .find(|(name, _)| meta.path.is_ident(name))
+            .ok_or_else(|| meta.error("unknown retry class, expected `transient`, `permanent`, `needs_operator` or `idempotent_success`"))?;
+        if class.is_some() {
+            return Err(meta.error("an error can only have one retry class"));
+        }
+        class = Some(variant);
+        Ok(())
+    })?;
+
+    let class = class.ok_or_else(|| syn::Error::new_spanned(attr, "`retry` needs a class, e.g. `#[retry(transient)]`"))?;
+    if let Some(backoff_ms) = &backoff_ms {
+        if class != "Transient" {
+            return Err(syn::Error::new_spanned(backoff_ms, "only a `transient` error can have a backoff"));
+        }
+    }
+
+    let class = quote::format_ident!("{}", class);
+    let backoff = match backoff_ms {
+        Some(backoff_ms) => {
+            let backoff_ms: u64 = backoff_ms.base10_parse()?;
+            quote! { Some(std::time::Duration::from_millis(#backoff_ms)) }
+        }
+        None => quote! { None },
+    };
+
+    Ok(Some(quote! {
+        ::bitcoin_rpc_errors::RetryHint { class: ::bitcoin_rpc_errors::RetryClass::#class, backoff: #backoff }
+    }))
+}
+
+// The retry hint of every variant, `None` for the ones without `#[retry(..)]`
+pub(crate) fn get_retry_hints(data_enum: &DataEnum) -> syn::Result<Vec<TokenStream>> {
+    let mut errors: Option<syn::Error> = None;
+    let mut hints = Vec::new();
+    for variant in &data_enum.variants {
+        match parse_retry(&variant.attrs) {
+            Ok(Some(hint)) => hints.push(quote! { Some(#hint) }),
+            Ok(None) => hints.push(quote! { None }),
+            Err(error) => crate::push_error(&mut errors, error),
+        }
+    }
+
+    match errors {
+        Some(errors) => Err(errors),
+        None => Ok(hints),
+    }
+}
@@ -0,0 +1,76 @@
+use regex::Regex;
+use regex_syntax::hir::{Class, Hir, HirKind};
+
+// Characters preferred when a class has to be represented in a sample
+const PREFERRED_CHARS: [char; 4] = ['a', '0', ' ', 'x'];
+
+fn sample_char(class: &Class) -> Option<char> {
+    match class {
+        Class::Unicode(class) => PREFERRED_CHARS
+            .into_iter()
+            .find(|c| class.ranges().iter().any(|range| range.start() <= *c && *c <= range.end()))
+            .or_else(|| class.ranges().first().map(|range| range.start())),
+        Class::Bytes(class) => PREFERRED_CHARS
+            .into_iter()
+            .find(|c| class.ranges().iter().any(|range| range.start() <= *c as u8 && *c as u8 <= range.end()))
+            .or_else(|| class.ranges().first().map(|range| range.start() as char)),
+    }
+}
+
+fn push_sample(hir: &Hir, sample: &mut String) {
+    match hir.kind() {
+        HirKind::Literal(literal) => sample.push_str(&String::from_utf8_lossy(&literal.0)),
+        HirKind::Class(class) => sample.extend(sample_char(class)),
+        HirKind::Repetition(repetition) => {
+            let count = match repetition.max {
+                Some(0) => 0,
+                _ => repetition.min.max(1),
+            };
+            for _ in 0..count {
+                push_sample(&repetition.sub, sample);
+            }
+        }
+        HirKind::Capture(capture) => push_sample(&capture.sub, sample),
+        HirKind::Concat(hirs) => {
+            for hir in hirs {
+                push_sample(hir, sample);
+            }
+        }
+        HirKind::Alternation(hirs) => {
+            if let Some(hir) = hirs.first() {
+                push_sample(hir, sample);
+            }
+        }
+        HirKind::Empty | HirKind::Look(_) => {}
+    }
+}
+
+// Builds a message that matches the pattern, e.g. "Missing amount for a" for
+// "^Missing amount for (.*)"
+pub fn sample_message(regex: &Regex) -> Option<String> {
+    let hir = regex_syntax::parse(regex.as_str()).ok()?;
+    let mut sample = String::new();
+    push_sample(&hir, &mut sample);
+
+    if regex.is_match(&sample) {
+        Some(sample)
+    } else {
+        None
+    }
+}
+
+// A variant is swallowed by another one if a message of its patterns is matched by
+// the other variant, but not the other way around. Returns such a message.
+pub fn swallowed_sample(specific: &[Regex], broad: &[Regex]) -> Option<String> {
+    let overlap = |samples: &[Regex], patterns: &[Regex]| {
+        samples
+            .iter()
+            .filter_map(sample_message)
+            .find(|sample| patterns.iter().any(|regex| regex.is_match(sample)))
+    };
+
+    match overlap(broad, specific) {
+        Some(_) => None,
+        None => overlap(specific, broad),
+    }
+}
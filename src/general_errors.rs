@@ -1,3 +1,5 @@
+use crate::{BlockValidityReason, PsbtError, ScriptError, TransactionError, TxDecodeReason};
+use bitcoin::{BlockHash, Txid};
 use proc_macro_magic::EnumError;
 
 #[derive(Clone, Debug, PartialEq, EnumError)]
@@ -6,12 +8,17 @@ pub enum MiscError {
     #[patterns("^Block header missing$")]
     BlockHeaderMissing,
 
+    // getblockfrompeer only fetches blocks a pruned node has seen before
     // https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/blockchain.cpp#L465
+    #[patterns("^In prune mode, only blocks that the node has already synced previously can be fetched from a peer$")]
+    #[remediation(flag = "-prune=0")]
+    #[retry(needs_operator)]
+    BlockNotSyncedPrunedNode,
+
     // https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/blockchain.cpp#L586
-    #[patterns(
-        "^In prune mode, only blocks that the node has already synced previously can be fetched from a peer$",
-        r"^Block not available \(pruned data\)$"
-    )]
+    #[patterns(r"^Block not available \(pruned data\)$")]
+    #[remediation(rpc = "getblockfrompeer", flag = "-prune=0", docs = "https://bitcoincore.org/en/doc/25.0.0/rpc/blockchain/getblockfrompeer/")]
+    #[retry(needs_operator)]
     BlockUnavailablePrunedNode,
 
     // https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/blockchain.cpp#L470
@@ -25,6 +32,8 @@ pub enum MiscError {
 
     // https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/blockchain.cpp#L610
     #[patterns(r"^Undo data not available \(pruned data\)$")]
+    #[remediation(flag = "-prune=0", docs = "https://github.com/bitcoin/bitcoin/blob/v25.0/doc/bitcoin-conf.md")]
+    #[retry(needs_operator)]
     UndoUnavailablePrunedNode,
 
     // https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/blockchain.cpp#L615
@@ -46,6 +55,8 @@ pub enum MiscError {
     // https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/blockchain.cpp#L2383
     // https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/blockchain.cpp#L2523
     #[patterns("^Index is not enabled for filtertype (.*)")]
+    #[remediation(flag = "-blockfilterindex", docs = "https://bitcoincore.org/en/doc/25.0.0/rpc/blockchain/getblockfilter/")]
+    #[retry(needs_operator)]
     IndexFilterForbidden(String),
 
     // https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/blockchain.cpp#L2400
@@ -59,6 +70,7 @@ pub enum MiscError {
     // TODO: Refactoring Core might be a good idea here
     // https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/blockchain.cpp#L2551
     #[patterns("^Filter not found. Block filters are still in the process of being indexed.$")]
+    #[retry(transient, backoff_ms = 5000)]
     IndexNotReady,
     
     // https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/external_signer.cpp#L43
@@ -69,8 +81,10 @@ pub enum MiscError {
     #[patterns("^Transaction is not in mempool$")]
     TxNotInMempool,
     
+    // savemempool until the mempool.dat of the last run is loaded at startup
     // https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/mempool.cpp#L742
     #[patterns("^The mempool was not loaded yet$")]
+    #[retry(transient, backoff_ms = 5000)]
     MempoolNotLoaded,
     
     // https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/mempool.cpp#L748
@@ -101,14 +115,14 @@ pub enum MiscError {
     
     // https://github.com/bitcoin/bitcoin/blob/v25.0/src/wallet/rpc/backup.cpp#L1425
     // https://github.com/bitcoin/bitcoin/blob/v25.0/src/wallet/rpc/backup.cpp#L1723
-    #[patterns(r"^Rescan failed for key with creation timestamp (.*). There was an error reading a \
-                block from time (.*), which is after or within (.*) seconds of key creation, and \
+    #[patterns("^Rescan failed for key with creation timestamp (?P<timestamp>\\d+). There was an error reading a \
+                block from time (?P<block_time>\\d+), which is after or within (?P<window>\\d+) seconds of key creation, and \
                 could contain transactions pertaining to the key. As a result, transactions \
                 and coins using this key may not appear in the wallet. This error could be \
-                caused by pruning or data corruption \(see bitcoind log for details\) and could \
-                be dealt with by downloading and rescanning the relevant blocks \(see -reindex \
-                option and rescanblockchain RPC\).$")]
-    RescanFailed(String, String, String),
+                caused by pruning or data corruption \\(see bitcoind log for details\\) and could \
+                be dealt with by downloading and rescanning the relevant blocks \\(see -reindex \
+                option and rescanblockchain RPC\\).$")]
+    RescanFailed { timestamp: i64, block_time: i64, window: i64 },
     
     // https://github.com/bitcoin/bitcoin/blob/v25.0/src/wallet/rpc/transactions.cpp#L907
     #[patterns("^Can't rescan beyond pruned data. Use RPC call getblockchaininfo to determine your pruned height.$")]
@@ -130,9 +144,20 @@ pub enum MiscError {
     // https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/external_signer.cpp#L56
     // https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/server.cpp#510
     // https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/spend.cpp#1125
+    #[fallback]
     Generic(String),
 }
 
+impl MiscError {
+    // Failures of sendrawtransaction or submitpackage
+    pub fn transaction_error(&self) -> Option<TransactionError> {
+        match self {
+            MiscError::Generic(message) => message.parse().ok(),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, EnumError)]
 pub enum TypeError {
     // https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/signmessage.cpp#L49
@@ -146,8 +171,8 @@ pub enum TypeError {
     MalformedBase64,
 
     // https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/util.cpp#L45
-    #[patterns("^JSON value of type (.*) for field (.*) is not of expected type (.*)")]
-    WrongJsonType(String, String, String),
+    #[patterns("^JSON value of type (?P<actual>.*) for field (?P<field>.*) is not of expected type (?P<expected>.*)")]
+    WrongJsonType { actual: String, field: String, expected: String },
 
     // TODO: Look for more like this
     // https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/util.cpp#L54
@@ -187,11 +212,13 @@ pub enum TypeError {
     MissingAmountForCoins(String),
 
     // https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/util.cpp#L46
-    // TODO: The capture can be an arbitrary string. We can not distinguish it from
-    // MissingKeyForProposal, MissingTimestamp or MissingAmountForCoins
-    // This only works because the variants are checked in order!
+    // The capture can be an arbitrary string, the more specific Missing* variants
+    // are declared first because this pattern swallows them
     #[patterns("^Missing (.*)")]
     Missing(String),
+
+    #[fallback]
+    Generic(String),
 }
 
 #[derive(Clone, Debug, PartialEq, EnumError)]
@@ -227,11 +254,11 @@ pub enum InvalidAddressOrKeyError {
     // https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/mining.cpp#L272
     // https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/mining.cpp#L321
     #[patterns("^Error: Invalid address$", "^Error: Invalid address or descriptor$")]
-    InvalidAddress,
+    InvalidAddressOrDescriptor,
 
     // https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/mining.cpp#L272
     #[patterns("^Transaction (.*) not in mempool.$")]
-    TxNotInMempool(String),
+    TxidNotInMempool(Txid),
 
     // https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/output_script.cpp#L131
     #[patterns("^Invalid public key: (.*)\n.$")]
@@ -260,13 +287,14 @@ pub enum InvalidAddressOrKeyError {
     InvalidPrivKey,
 
     // https://github.com/bitcoin/bitcoin/blob/v25.0/src/wallet/rpc/wallet.cpp#L559
-    #[patterns("^Already have this key \(either as an HD seed or as a loose private key\)$")]
+    #[patterns(r"^Already have this key \(either as an HD seed or as a loose private key\)$")]
     KeyAlreadyPresent,
 
     // https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/mining.cpp#L226
     // https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/output_script.cpp#L202
     // https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/output_script.cpp#L261
     // https://github.com/bitcoin/bitcoin/blob/v25.0/src/wallet/rpc/signmessage.cpp#L61
+    #[fallback]
     Generic(String)
 }
 
@@ -275,6 +303,61 @@ pub enum OutOfMemoryError {
     // https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/mining.cpp#L759
     #[patterns("^Out of memory$")]
     OutOfMemory,
+
+    #[fallback]
+    Generic(String),
+}
+
+#[derive(Clone, Debug, PartialEq, EnumError)]
+pub enum InvalidParameterError {
+    // https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/server.cpp
+    #[patterns("^Unknown named parameter (.*)$")]
+    UnknownNamedParameter(String),
+
+    // https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/server.cpp
+    #[patterns("^Parameter (.*) specified twice both as positional and named argument$")]
+    ParameterSpecifiedTwice(String),
+
+    // https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/blockchain.cpp
+    #[patterns("^Block height out of range$")]
+    BlockHeightOutOfRange,
+
+    // https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/rawtransaction_util.cpp
+    #[patterns("^Invalid parameter, duplicated address: (.*)$")]
+    DuplicatedAddress(String),
+
+    // https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/rawtransaction_util.cpp
+    #[patterns("^Invalid parameter, missing vout key$")]
+    MissingVout,
+
+    // https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/rawtransaction_util.cpp
+    #[patterns("^Invalid parameter, vout cannot be negative$")]
+    NegativeVout,
+
+    // https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/rawtransaction_util.cpp
+    #[patterns("^Invalid parameter, sequence number is out of range$")]
+    SequenceOutOfRange,
+
+    // https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/util.cpp
+    #[patterns("^Invalid estimate_mode parameter, must be one of: (.*)$")]
+    InvalidEstimateMode(String),
+
+    // https://github.com/bitcoin/bitcoin/blob/v25.0/src/wallet/rpc/spend.cpp
+    #[patterns("^Cannot specify both conf_target and fee_rate")]
+    ConfTargetAndFeeRate,
+
+    #[fallback]
+    Generic(String),
+}
+
+impl InvalidParameterError {
+    // An invalid or mismatching PSBT, Core sends both as -8
+    pub fn transaction_error(&self) -> Option<TransactionError> {
+        match self {
+            InvalidParameterError::Generic(message) => message.parse().ok(),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, EnumError)]
@@ -286,6 +369,7 @@ pub enum DatabaseError {
     // https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/blockchain.cpp#L1594
     // https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/blockchain.cpp#L1513
     // https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/blockchain.cpp#L1554
+    #[fallback]
     Generic(String),
 }
 
@@ -344,7 +428,7 @@ pub enum DeserializationError {
     // https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/rawtransaction.cpp#L1821
     // https://github.com/bitcoin/bitcoin/blob/v25.0/src/wallet/rpc/spend.cpp#L1577
     #[patterns("^TX decode failed (.*)")]
-    TxDecodeFailed(String),
+    TxDecodeFailed(TxDecodeReason),
 
     // https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/rawtransaction.cpp#L402 
     // https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/rawtransaction.cpp#L1549
@@ -367,18 +451,37 @@ pub enum DeserializationError {
     // No Pattern
     // https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/util.cpp#L345
     // https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/util.cpp#L347
+    #[fallback]
     Generic(String),
 }
 
+impl DeserializationError {
+    // A sighash mismatch of walletprocesspsbt or descriptorprocesspsbt
+    pub fn psbt_error(&self) -> Option<PsbtError> {
+        match self {
+            DeserializationError::Generic(message) => message.parse().ok(),
+            _ => None,
+        }
+    }
+
+    // A sighash mismatch of a transaction, Core sends it as -22
+    pub fn transaction_error(&self) -> Option<TransactionError> {
+        match self {
+            DeserializationError::Generic(message) => message.parse().ok(),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, EnumError)]
 pub enum VerifyError {
     // https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/mining.cpp#L379
     #[patterns("^TestBlockValidity failed: (.*)")]
-    BlockValidityFailed(String),
+    BlockValidityFailed(BlockValidityReason),
 
     // https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/mining.cpp#L1030
     #[patterns(r"^Must submit previous header \((.*)\) first$")]
-    PreviousHeaderMissing(String),
+    PreviousHeaderMissing(BlockHash),
 
     // https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/rawtransaction.cpp#L612
     #[patterns("^Input not found or already spent$")]
@@ -388,12 +491,58 @@ pub enum VerifyError {
     // https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/mining.cpp#L491
     // https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/mining.cpp#L1038
     // https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/mining.cpp#L1040
+    #[fallback]
     Generic(String),
 }
 
+impl VerifyError {
+    // Failures of finalizepsbt, walletprocesspsbt or an external signer
+    pub fn psbt_error(&self) -> Option<PsbtError> {
+        match self {
+            VerifyError::Generic(message) => message.parse().ok(),
+            _ => None,
+        }
+    }
+
+    // Failures of sendrawtransaction or the wallet when broadcasting a transaction
+    pub fn transaction_error(&self) -> Option<TransactionError> {
+        match self {
+            VerifyError::Generic(message) => message.parse().ok(),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, EnumError)]
+pub enum VerifyRejectedError {
+    // https://github.com/bitcoin/bitcoin/blob/v25.0/src/validation.cpp
+    #[patterns(r"^mandatory-script-verify-flag-failed \((.*)\)$")]
+    MandatoryScriptVerifyFlagFailed(ScriptError),
+
+    // https://github.com/bitcoin/bitcoin/blob/v25.0/src/validation.cpp
+    #[patterns(r"^non-mandatory-script-verify-flag \((.*)\)$")]
+    NonMandatoryScriptVerifyFlag(ScriptError),
+
+    // Contains the reject reason of the mempool, e.g. "min relay fee not met"
+    // https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/util.cpp
+    #[fallback]
+    Generic(String),
+}
+
+impl VerifyRejectedError {
+    // Failures of sendrawtransaction if the mempool did not report a reject reason
+    pub fn transaction_error(&self) -> Option<TransactionError> {
+        match self {
+            VerifyRejectedError::Generic(message) => message.parse().ok(),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, EnumError)]
 pub enum WarmupError {
     // https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/server.cpp#L483
+    #[fallback]
     Generic(String),
 }
 
@@ -402,4 +551,200 @@ pub enum MethodDeprecated {
     // https://github.com/bitcoin/bitcoin/blob/v25.0/src/wallet/rpc/coins.cpp#L198
     #[patterns(r#"^dummy first argument must be excluded or set to "*".$"#)]
     WrongDummyArgument,
+
+    #[fallback]
+    Generic(String),
+}
+
+// https://github.com/bitcoin/bitcoin/blob/v25.0/src/wallet/rpc/util.cpp
+#[derive(Clone, Debug, PartialEq, EnumError)]
+pub enum WalletUnlockNeededError {
+    #[patterns("^Error: Please enter the wallet passphrase with walletpassphrase first.$")]
+    PassphraseRequired,
+
+    #[fallback]
+    Generic(String),
+}
+
+// https://github.com/bitcoin/bitcoin/blob/v25.0/src/wallet/rpc/encrypt.cpp
+#[derive(Clone, Debug, PartialEq, EnumError)]
+pub enum WalletPassphraseIncorrectError {
+    #[patterns("^Error: The wallet passphrase entered was incorrect.$")]
+    Incorrect,
+
+    // Passphrases set before v25.0 were truncated at the first null character, older releases
+    // report them as incorrect without a reason
+    #[patterns(since = "25.0", "^The wallet passphrase entered is incorrect. It contains a null character")]
+    ContainsNullCharacter,
+
+    #[patterns(since = "25.0", "^The old wallet passphrase entered is incorrect. It contains a null character")]
+    OldContainsNullCharacter,
+
+    #[fallback]
+    Generic(String),
+}
+
+// https://github.com/bitcoin/bitcoin/blob/v25.0/src/wallet/rpc/encrypt.cpp
+#[derive(Clone, Debug, PartialEq, EnumError)]
+pub enum WalletWrongEncStateError {
+    // Contains the called RPC: walletpassphrase, walletpassphrasechange or walletlock
+    #[patterns("^Error: running with an unencrypted wallet, but (.*) was called.$")]
+    NotEncrypted(String),
+
+    #[patterns("^Error: running with an encrypted wallet, but encryptwallet was called.$")]
+    AlreadyEncrypted,
+
+    #[fallback]
+    Generic(String),
+}
+
+// https://github.com/bitcoin/bitcoin/blob/v25.0/src/wallet/rpc/encrypt.cpp
+#[derive(Clone, Debug, PartialEq, EnumError)]
+pub enum WalletEncryptionFailedError {
+    #[patterns("^Error: wallet does not contain private keys, nothing to encrypt.$")]
+    NoPrivateKeys,
+
+    #[patterns("^Error: Failed to encrypt the wallet.$")]
+    EncryptionFailed,
+
+    #[fallback]
+    Generic(String),
+}
+
+#[derive(Clone, Debug, PartialEq, EnumError)]
+pub enum WalletAlreadyUnlockedError {
+    // Not thrown by v25.0, older releases refused to call walletpassphrase on an unlocked wallet
+    #[patterns("^Error: Wallet is already unlocked, use walletlock first if need to change unlock settings.$")]
+    AlreadyUnlocked,
+
+    #[fallback]
+    Generic(String),
+}
+
+// https://github.com/bitcoin/bitcoin/blob/v25.0/src/wallet/rpc/util.cpp
+#[derive(Clone, Debug, PartialEq, EnumError)]
+pub enum WalletNotFoundError {
+    #[patterns("^Requested wallet does not exist or is not loaded$")]
+    #[remediation(rpc = "loadwallet", docs = "https://bitcoincore.org/en/doc/25.0.0/rpc/wallet/loadwallet/")]
+    NotLoaded,
+
+    #[patterns("^No wallet is loaded. Load a wallet using loadwallet or create a new one with createwallet.")]
+    #[remediation(rpc = "loadwallet", docs = "https://bitcoincore.org/en/doc/25.0.0/rpc/wallet/loadwallet/")]
+    NoWalletLoaded,
+
+    // Contains the database path
+    // https://github.com/bitcoin/bitcoin/blob/v25.0/src/wallet/walletdb.cpp
+    #[patterns("^(?:Wallet file verification failed. )?Failed to load database path '(.*)'. Path does not exist.$")]
+    PathDoesNotExist(String),
+
+    #[fallback]
+    Generic(String),
+}
+
+// https://github.com/bitcoin/bitcoin/blob/v25.0/src/wallet/rpc/util.cpp
+#[derive(Clone, Debug, PartialEq, EnumError)]
+pub enum WalletNotSpecifiedError {
+    #[patterns(r"^Wallet file not specified \(must request wallet RPC through /wallet/<filename> uri-path\).$")]
+    #[remediation(flag = "-rpcwallet", docs = "https://github.com/bitcoin/bitcoin/blob/v25.0/doc/managing-wallets.md")]
+    NotSpecified,
+
+    #[fallback]
+    Generic(String),
+}
+
+// https://github.com/bitcoin/bitcoin/blob/v25.0/src/wallet/rpc/wallet.cpp
+#[derive(Clone, Debug, PartialEq, EnumError)]
+pub enum WalletAlreadyLoadedError {
+    // Contains the wallet name
+    #[patterns(r#"^Wallet "(.*)" is already loaded.$"#)]
+    AlreadyLoaded(String),
+
+    // Contains the path of the data file
+    // https://github.com/bitcoin/bitcoin/blob/v25.0/src/wallet/bdb.cpp
+    #[patterns("^(?:Wallet file verification failed. )?Refusing to load database. Data file '(.*)' is already loaded.$")]
+    #[retry(needs_operator)]
+    DataFileAlreadyLoaded(String),
+
+    #[fallback]
+    Generic(String),
+}
+
+// https://github.com/bitcoin/bitcoin/blob/v25.0/src/wallet/walletdb.cpp
+#[derive(Clone, Debug, PartialEq, EnumError)]
+pub enum WalletAlreadyExistsError {
+    // Contains the database path
+    #[patterns("^(?:Wallet file verification failed. )?Failed to create database path '(.*)'. Database already exists.$")]
+    DatabaseAlreadyExists(String),
+
+    #[fallback]
+    Generic(String),
+}
+
+// https://github.com/bitcoin/bitcoin/blob/v25.0/src/wallet/rpc/util.cpp
+#[derive(Clone, Debug, PartialEq, EnumError)]
+pub enum WalletInvalidLabelNameError {
+    #[patterns("^Invalid label name$")]
+    InvalidLabelName,
+
+    // Contains the reserved label
+    #[patterns("^Label '(.*)' is reserved$")]
+    ReservedLabel(String),
+
+    #[fallback]
+    Generic(String),
+}
+
+// getnewaddress and getrawchangeaddress
+// https://github.com/bitcoin/bitcoin/blob/v25.0/src/wallet/rpc/addresses.cpp
+#[derive(Clone, Debug, PartialEq, EnumError)]
+pub enum WalletKeypoolRanOutError {
+    // Legacy wallet without keys left in the keypool
+    // https://github.com/bitcoin/bitcoin/blob/v25.0/src/wallet/scriptpubkeyman.cpp
+    // https://github.com/bitcoin/bitcoin/blob/v25.0/src/wallet/wallet.cpp
+    #[patterns("^Error: Keypool ran out, please call keypoolrefill first$")]
+    #[remediation(rpc = "keypoolrefill", docs = "https://bitcoincore.org/en/doc/25.0.0/rpc/wallet/keypoolrefill/")]
+    KeypoolRanOut,
+
+    // Wallet with private keys disabled and an empty keypool
+    #[patterns("^Error: This wallet has no available keys$")]
+    NoAvailableKeys,
+
+    // Descriptor wallet that reached the end of the range of its active descriptor
+    // https://github.com/bitcoin/bitcoin/blob/v25.0/src/wallet/scriptpubkeyman.cpp
+    #[patterns("^No addresses available$")]
+    #[remediation(rpc = "keypoolrefill", docs = "https://bitcoincore.org/en/doc/25.0.0/rpc/wallet/keypoolrefill/")]
+    NoAddressesAvailable,
+
+    // Descriptor wallet without an active descriptor for the requested output type
+    // https://github.com/bitcoin/bitcoin/blob/v25.0/src/wallet/wallet.cpp
+    #[patterns("^Error: No (.*) addresses available.$")]
+    #[remediation(rpc = "importdescriptors", docs = "https://bitcoincore.org/en/doc/25.0.0/rpc/wallet/importdescriptors/")]
+    NoDescriptorForOutputType(String),
+
+    #[fallback]
+    Generic(String),
+}
+
+impl WalletKeypoolRanOutError {
+    // A keypoolrefill does not help if the wallet lacks an active descriptor for the
+    // output type, a descriptor has to be imported instead
+    pub fn is_missing_descriptor(&self) -> bool {
+        matches!(self, WalletKeypoolRanOutError::NoDescriptorForOutputType(_))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, EnumError)]
+pub enum WalletError {
+    // https://github.com/bitcoin/bitcoin/blob/v25.0/src/wallet/rpc/spend.cpp
+    #[fallback]
+    Generic(String),
+}
+
+impl WalletError {
+    // Failures of walletprocesspsbt or send when signing the PSBT
+    pub fn psbt_error(&self) -> Option<PsbtError> {
+        match self {
+            WalletError::Generic(message) => message.parse().ok(),
+        }
+    }
 }
@@ -1,125 +1,340 @@
+// The code generated by `EnumError` refers to this crate by name
+extern crate self as bitcoin_rpc_errors;
+
 mod general_errors;
+#[cfg(feature = "tokio")]
+mod retry;
+mod transaction_errors;
+mod validation_errors;
 
 #[cfg(test)]
 mod test;
 
-use crate::general_errors::*;
+pub use crate::general_errors::*;
+#[cfg(feature = "tokio")]
+pub use crate::retry::{Attempt, RetryPolicy};
+pub use crate::transaction_errors::*;
+pub use crate::validation_errors::*;
+
+use proc_macro_magic::ErrorCode;
+
+// Used by the generated code, not part of the API
+#[doc(hidden)]
+pub mod __private {
+    pub use regex;
+
+    use crate::CoreVersion;
+
+    // Implemented by `EnumError` for enums with a `#[fallback]`, the only ones a code of
+    // `ErrorCode` can wrap since parsing the message must not fail
+    #[diagnostic::on_unimplemented(
+        message = "`{Self}` needs a `#[fallback]` variant to be wrapped by a code of `ErrorCode`",
+        label = "`{Self}` has no `#[fallback]` variant",
+        note = "the `#[fallback]` variant keeps the messages no pattern matched"
+    )]
+    pub trait WithFallback: Sized {
+        fn parse_message(message: &str, version: Option<CoreVersion>) -> Self;
+    }
+}
+
+// A variant of an enum deriving `EnumError`, see `VARIANTS` of the enum
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VariantInfo {
+    pub name: &'static str,
+    pub patterns: &'static [&'static str],
+    // Number of captures of every pattern
+    pub captures: usize,
+    // Takes the messages no pattern matched
+    pub fallback: bool,
+    pub remediation: Option<Remediation>,
+    pub retry: Option<RetryHint>,
+}
+
+// What an operator can do about an error, declared with `#[remediation(..)]`
+// next to the patterns of a variant or on a code
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Remediation {
+    // An RPC that resolves the error, e.g. keypoolrefill
+    pub rpc: Option<&'static str>,
+    // A startup option of the node, e.g. -blockfilterindex
+    pub flag: Option<&'static str>,
+    // Where the RPC or the option is documented
+    pub docs: Option<&'static str>,
+}
+
+// An error code of protocol.h, see `Error::VARIANTS`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CodeInfo {
+    pub name: &'static str,
+    pub code: i32,
+    // Declared with `#[code(.., category = ..)]`
+    pub category: ErrorCategory,
+    // The enum the message is parsed into, None if the message is ignored
+    pub enum_name: Option<&'static str>,
+    // Variants of the enum the message is parsed into, empty if the message is ignored
+    pub variants: &'static [VariantInfo],
+    // Declared with `#[remediation(..)]` on the code, a variant can override it
+    pub remediation: Option<Remediation>,
+    // Declared with `#[retry(..)]` on the code, a variant can override it
+    pub retry: Option<RetryHint>,
+}
+
+// Whether a call that failed with an error is worth repeating
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RetryClass {
+    // The node is busy or not ready yet, e.g. warming up or still indexing
+    Transient,
+    // The same call fails again, e.g. an invalid parameter
+    Permanent,
+    // Someone has to act first, e.g. unlock the wallet or change an option
+    NeedsOperator,
+    // What the call wanted is already done, e.g. the transaction is in the chain
+    IdempotentSuccess,
+}
+
+// Declared with `#[retry(transient, backoff_ms = 1000)]` on a code or a variant
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct RetryHint {
+    pub class: RetryClass,
+    // How long to wait before the next attempt, only transient errors have one
+    pub backoff: Option<std::time::Duration>,
+}
+
+impl RetryHint {
+    // Errors without a hint and unknown errors are not retried
+    pub const PERMANENT: RetryHint = RetryHint { class: RetryClass::Permanent, backoff: None };
+
+    // A full work queue is answered with HTTP 503 and a plain text body, not with a JSON-RPC error
+    // https://github.com/bitcoin/bitcoin/blob/v25.0/src/httpserver.cpp
+    pub fn for_http_response(status: u16, body: &str) -> Option<RetryHint> {
+        if status == 503 && body.trim() == "Work queue depth exceeded" {
+            return Some(RetryHint { class: RetryClass::Transient, backoff: Some(std::time::Duration::from_millis(500)) });
+        }
+
+        None
+    }
+}
+
+// A Bitcoin Core release like 25.0, patterns can be limited to a range of releases
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CoreVersion {
+    pub major: u32,
+    pub minor: u32,
+}
+
+impl CoreVersion {
+    pub const fn new(major: u32, minor: u32) -> Self {
+        CoreVersion { major, minor }
+    }
+
+    // `since` is inclusive and `until` exclusive, a missing bound is open
+    pub fn is_within(self, since: Option<CoreVersion>, until: Option<CoreVersion>) -> bool {
+        since.is_none_or(|since| self >= since) && until.is_none_or(|until| self < until)
+    }
+}
+
+impl std::str::FromStr for CoreVersion {
+    type Err = ();
+
+    // Parses "25.0", a leading "v" and a patch version like in "v25.1.0" are ignored
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.strip_prefix('v').unwrap_or(s).split('.');
+        let major = parts.next().ok_or(())?.parse().map_err(|_| ())?;
+        let minor = parts.next().ok_or(())?.parse().map_err(|_| ())?;
+
+        Ok(CoreVersion { major, minor })
+    }
+}
+
+impl std::fmt::Display for CoreVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+// The groups of codes in protocol.h
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ErrorCategory {
+    General,
+    P2pClient,
+    Chain,
+    Wallet,
+    Unknown,
+}
 
 // https://github.com/bitcoin/bitcoin/blob/master/src/rpc/protocol.h
 #[allow(non_camel_case_types)]
 #[rustfmt::skip]
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, ErrorCode)]
 pub enum Error {
     // General application defined errors
-    RPC_MISC_ERROR(MiscError),                          // std::exception thrown in command handling
-    RPC_TYPE_ERROR(TypeError),                          // Unexpected type was passed as parameter
-    RPC_INVALID_ADDRESS_OR_KEY,                         // Invalid address or key
-    RPC_OUT_OF_MEMORY(OutOfMemoryError),                // Ran out of memory during operation - No sub erros needed
-    RPC_INVALID_PARAMETER,                              // Invalid, missing or duplicate parameter
-    RPC_DATABASE_ERROR(DatabaseError),                  // Database error
-    RPC_DESERIALIZATION_ERROR(DeserializationError),    // Error parsing or validating structure in raw format
-    RPC_VERIFY_ERROR(VerifyError),                      // General error during transaction or block submission
-    RPC_VERIFY_REJECTED,                                // Transaction or block was rejected by network rules
-    RPC_VERIFY_ALREADY_IN_CHAIN,                        // Transaction already in chain
-    RPC_IN_WARMUP(WarmupError),                         // Client still warming up
-    RPC_METHOD_DEPRECATED(MethodDeprecated),            // RPC method is deprecated
+    #[code(-1, category = General)]  RPC_MISC_ERROR(MiscError),                            // std::exception thrown in command handling
+    #[retry(permanent)]
+    #[code(-3, category = General)]  RPC_TYPE_ERROR(TypeError),                            // Unexpected type was passed as parameter
+    #[code(-5, category = General)]  RPC_INVALID_ADDRESS_OR_KEY(InvalidAddressOrKeyError), // Invalid address or key
+    #[code(-7, category = General)]  RPC_OUT_OF_MEMORY(OutOfMemoryError),                  // Ran out of memory during operation, no sub errors needed
+    #[retry(permanent)]
+    #[code(-8, category = General)]  RPC_INVALID_PARAMETER(InvalidParameterError),         // Invalid, missing or duplicate parameter
+    #[code(-20, category = General)] RPC_DATABASE_ERROR(DatabaseError),                    // Database error
+    #[retry(permanent)]
+    #[code(-22, category = General)] RPC_DESERIALIZATION_ERROR(DeserializationError),      // Error parsing or validating structure in raw format
+    #[code(-25, category = General)] RPC_VERIFY_ERROR(VerifyError),                        // General error during transaction or block submission
+    #[code(-26, category = General)] RPC_VERIFY_REJECTED(VerifyRejectedError),             // Transaction or block was rejected by network rules
+    #[retry(idempotent_success)]
+    #[code(-27, category = General)] RPC_VERIFY_ALREADY_IN_CHAIN,                          // Transaction already in chain
+    #[retry(transient, backoff_ms = 1000)]
+    #[code(-28, category = General)] RPC_IN_WARMUP(WarmupError),                           // Client still warming up
+    #[code(-32, category = General)] RPC_METHOD_DEPRECATED(MethodDeprecated),              // RPC method is deprecated
 
     // P2P client errors
-    RPC_CLIENT_NOT_CONNECTED,         // Bitcoin is not connected
-    RPC_CLIENT_IN_INITIAL_DOWNLOAD,   // Still downloading initial blocks
-    RPC_CLIENT_NODE_ALREADY_ADDED,    // Node is already added
-    RPC_CLIENT_NODE_NOT_ADDED,        // Node has not been added before
-    RPC_CLIENT_NODE_NOT_CONNECTED,    // Node to disconnect not found in connected nodes
-    RPC_CLIENT_INVALID_IP_OR_SUBNET,  // Invalid IP/Subnet
-    RPC_CLIENT_P2P_DISABLED,          // No valid connection manager instance found
-    RPC_CLIENT_NODE_CAPACITY_REACHED, // Max number of outbound or block-relay connections already open
+    #[retry(transient, backoff_ms = 5000)]
+    #[code(-9, category = P2pClient)]  RPC_CLIENT_NOT_CONNECTED,         // Bitcoin is not connected
+    #[retry(transient, backoff_ms = 60000)]
+    #[code(-10, category = P2pClient)] RPC_CLIENT_IN_INITIAL_DOWNLOAD,   // Still downloading initial blocks
+    #[code(-23, category = P2pClient)] RPC_CLIENT_NODE_ALREADY_ADDED,    // Node is already added
+    #[code(-24, category = P2pClient)] RPC_CLIENT_NODE_NOT_ADDED,        // Node has not been added before
+    #[code(-29, category = P2pClient)] RPC_CLIENT_NODE_NOT_CONNECTED,    // Node to disconnect not found in connected nodes
+    #[code(-30, category = P2pClient)] RPC_CLIENT_INVALID_IP_OR_SUBNET,  // Invalid IP/Subnet
+    #[retry(needs_operator)]
+    #[code(-31, category = P2pClient)] RPC_CLIENT_P2P_DISABLED,          // No valid connection manager instance found
+    #[code(-34, category = P2pClient)] RPC_CLIENT_NODE_CAPACITY_REACHED, // Max number of outbound or block-relay connections already open
 
     // Chain errors
-    RPC_CLIENT_MEMPOOL_DISABLED, // No mempool instance found
+    #[retry(needs_operator)]
+    #[code(-33, category = Chain)] RPC_CLIENT_MEMPOOL_DISABLED, // No mempool instance found
 
     // Wallet errors
-    RPC_WALLET_ERROR,                // Unspecified problem with wallet (key not found etc.)
-    RPC_WALLET_INSUFFICIENT_FUNDS,   // Not enough funds in wallet or account
-    RPC_WALLET_INVALID_LABEL_NAME,   // Invalid label name
-    RPC_WALLET_KEYPOOL_RAN_OUT,      // Keypool ran out, call keypoolrefill first
-    RPC_WALLET_UNLOCK_NEEDED,        // Enter the wallet passphrase with walletpassphrase first
-    RPC_WALLET_PASSPHRASE_INCORRECT, // The wallet passphrase entered was incorrect
-    RPC_WALLET_WRONG_ENC_STATE,      // Command given in wrong wallet encryption state (encrypting an encrypted wallet etc.)
-    RPC_WALLET_ENCRYPTION_FAILED,    // Failed to encrypt the wallet
-    RPC_WALLET_ALREADY_UNLOCKED,     // Wallet is already unlocked
-    RPC_WALLET_NOT_FOUND,            // Invalid wallet specified
-    RPC_WALLET_NOT_SPECIFIED,        // No wallet specified (error when there are multiple wallets loaded)
-    RPC_WALLET_ALREADY_LOADED,       // This same wallet is already loaded
-    RPC_WALLET_ALREADY_EXISTS,       // There is already a wallet with the same name
+    #[code(-4, category = Wallet)]  RPC_WALLET_ERROR(WalletError),                                   // Unspecified problem with wallet (key not found etc.)
+    #[code(-6, category = Wallet)]  RPC_WALLET_INSUFFICIENT_FUNDS,                                   // Not enough funds in wallet or account
+    #[code(-11, category = Wallet)] RPC_WALLET_INVALID_LABEL_NAME(WalletInvalidLabelNameError),      // Invalid label name
+    #[retry(needs_operator)]
+    #[code(-12, category = Wallet)] RPC_WALLET_KEYPOOL_RAN_OUT(WalletKeypoolRanOutError),            // Keypool ran out, call keypoolrefill first
+    #[remediation(rpc = "walletpassphrase", docs = "https://bitcoincore.org/en/doc/25.0.0/rpc/wallet/walletpassphrase/")]
+    #[retry(needs_operator)]
+    #[code(-13, category = Wallet)] RPC_WALLET_UNLOCK_NEEDED(WalletUnlockNeededError),               // Enter the wallet passphrase with walletpassphrase first
+    #[code(-14, category = Wallet)] RPC_WALLET_PASSPHRASE_INCORRECT(WalletPassphraseIncorrectError), // The wallet passphrase entered was incorrect
+    #[code(-15, category = Wallet)] RPC_WALLET_WRONG_ENC_STATE(WalletWrongEncStateError),            // Command given in wrong wallet encryption state (encrypting an encrypted wallet etc.)
+    #[code(-16, category = Wallet)] RPC_WALLET_ENCRYPTION_FAILED(WalletEncryptionFailedError),       // Failed to encrypt the wallet
+    #[retry(idempotent_success)]
+    #[code(-17, category = Wallet)] RPC_WALLET_ALREADY_UNLOCKED(WalletAlreadyUnlockedError),         // Wallet is already unlocked
+    #[retry(needs_operator)]
+    #[code(-18, category = Wallet)] RPC_WALLET_NOT_FOUND(WalletNotFoundError),                       // Invalid wallet specified
+    #[code(-19, category = Wallet)] RPC_WALLET_NOT_SPECIFIED(WalletNotSpecifiedError),               // No wallet specified (error when there are multiple wallets loaded)
+    #[retry(idempotent_success)]
+    #[code(-35, category = Wallet)] RPC_WALLET_ALREADY_LOADED(WalletAlreadyLoadedError),             // This same wallet is already loaded
+    #[code(-36, category = Wallet)] RPC_WALLET_ALREADY_EXISTS(WalletAlreadyExistsError),             // There is already a wallet with the same name
 
     // Unknown Error
-    RPC_UNKOWN_ERROR(i32, String),
+    #[fallback]
+    RPC_UNKOWN_ERROR(i32, String), // Error code is not defined by Bitcoin core
+}
+
+impl Error {
+    // Transaction errors are spread across several codes, this gives a failed broadcast
+    // the same meaning no matter which code carried it
+    pub fn transaction_error(&self) -> Option<TransactionError> {
+        match self {
+            Error::RPC_MISC_ERROR(e) => e.transaction_error(),
+            Error::RPC_INVALID_PARAMETER(e) => e.transaction_error(),
+            Error::RPC_DESERIALIZATION_ERROR(e) => e.transaction_error(),
+            Error::RPC_VERIFY_ERROR(e) => e.transaction_error(),
+            Error::RPC_VERIFY_REJECTED(e) => e.transaction_error(),
+            Error::RPC_VERIFY_ALREADY_IN_CHAIN => Some(TransactionError::AlreadyInChain),
+            Error::RPC_CLIENT_P2P_DISABLED => Some(TransactionError::P2pDisabled),
+            _ => None,
+        }
+    }
+
+    // Only tries the message patterns of the given Core version, e.g. for a
+    // fleet of nodes running different releases
+    #[allow(clippy::result_unit_err)]
+    pub fn parse_for_version(s: &str, version: CoreVersion) -> Result<Self, ()> {
+        let (code, message) = parse_envelope(s)?;
+        Ok(Error::from_code_for_version(code, &message, version))
+    }
 }
 
 impl std::str::FromStr for Error {
     type Err = ();
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let regex = regex::Regex::new(concat!(
-            r#"\{"#, "[[:space:]]*", 
-            r#""code":"#, "[[:space:]]*",
-            r#"(-?\d+),"#, "[[:space:]]*",
-            r#""message":"#, "[[:space:]]*",
-            r#""(.*?)""#, "[[:space:]]*",
-            r#"\}"#,
-        )).unwrap();
-        let captures = regex.captures(s).ok_or(())?;
-
-        let code: i32 = captures
-            .get(1)
-            .ok_or(())?
-            .as_str()
-            .parse()
-            .map_err(|_| ())?;
-        let message: String = captures.get(2).ok_or(())?.as_str().to_string();
-
-        Ok(match (code, &message) {
-            // General application defined errors
-            (-1, m) => Error::RPC_MISC_ERROR(m.parse().unwrap()),
-            (-3, m) => Error::RPC_TYPE_ERROR(m.parse().unwrap()),
-            (-5, _) => Error::RPC_INVALID_ADDRESS_OR_KEY,
-            (-7, m) => Error::RPC_OUT_OF_MEMORY(m.parse().unwrap()),
-            (-8, _) => Error::RPC_INVALID_PARAMETER,
-            (-20, m) => Error::RPC_DATABASE_ERROR(m.parse().unwrap()),
-            (-22, m) => Error::RPC_DESERIALIZATION_ERROR(m.parse().unwrap()),
-            (-25, m) => Error::RPC_VERIFY_ERROR(m.parse().unwrap()),
-            (-26, _) => Error::RPC_VERIFY_REJECTED,
-            (-27, _) => Error::RPC_VERIFY_ALREADY_IN_CHAIN,
-            (-28, m) => Error::RPC_IN_WARMUP(m.parse().unwrap()),
-            (-32, m) => Error::RPC_METHOD_DEPRECATED(m.parse().unwrap()),
-
-            // P2P client errors
-            (-9, _) => Error::RPC_CLIENT_NOT_CONNECTED,
-            (-10, _) => Error::RPC_CLIENT_IN_INITIAL_DOWNLOAD,
-            (-23, _) => Error::RPC_CLIENT_NODE_ALREADY_ADDED,
-            (-24, _) => Error::RPC_CLIENT_NODE_NOT_ADDED,
-            (-29, _) => Error::RPC_CLIENT_NODE_NOT_CONNECTED,
-            (-30, _) => Error::RPC_CLIENT_INVALID_IP_OR_SUBNET,
-            (-31, _) => Error::RPC_CLIENT_P2P_DISABLED,
-            (-34, _) => Error::RPC_CLIENT_NODE_CAPACITY_REACHED,
-
-            // Wallet errors
-            (-4, _) => Error::RPC_WALLET_ERROR,
-            (-6, _) => Error::RPC_WALLET_INSUFFICIENT_FUNDS,
-            (-11, _) => Error::RPC_WALLET_INVALID_LABEL_NAME,
-            (-12, _) => Error::RPC_WALLET_KEYPOOL_RAN_OUT,
-            (-13, _) => Error::RPC_WALLET_UNLOCK_NEEDED,
-            (-14, _) => Error::RPC_WALLET_PASSPHRASE_INCORRECT,
-            (-15, _) => Error::RPC_WALLET_WRONG_ENC_STATE,
-            (-16, _) => Error::RPC_WALLET_ENCRYPTION_FAILED,
-            (-17, _) => Error::RPC_WALLET_ALREADY_UNLOCKED,
-            (-18, _) => Error::RPC_WALLET_NOT_FOUND,
-            (-19, _) => Error::RPC_WALLET_NOT_SPECIFIED,
-            (-35, _) => Error::RPC_WALLET_ALREADY_LOADED,
-            (-36, _) => Error::RPC_WALLET_ALREADY_EXISTS,
-
-            // Unknown Error
-            _ => Error::RPC_UNKOWN_ERROR(code, message),
-        })
+        let (code, message) = parse_envelope(s)?;
+        Ok(Error::from_code(code, &message))
     }
 }
+
+// Extracts code and message of the JSON error object
+fn parse_envelope(s: &str) -> Result<(i32, String), ()> {
+    // Compiled once, the envelope is parsed for every error
+    static ENVELOPE: std::sync::OnceLock<regex::Regex> = std::sync::OnceLock::new();
+    let regex = ENVELOPE.get_or_init(|| regex::Regex::new(concat!(
+        r#"\{"#, "[[:space:]]*", 
+        r#""code":"#, "[[:space:]]*",
+        r#"(-?\d+),"#, "[[:space:]]*",
+        r#""message":"#, "[[:space:]]*",
+        r#""((?:[^"\\]|\\.)*)""#, "[[:space:]]*",
+        r#"\}"#,
+    )).unwrap());
+    let captures = regex.captures(s).ok_or(())?;
+
+    let code: i32 = captures
+        .get(1)
+        .ok_or(())?
+        .as_str()
+        .parse()
+        .map_err(|_| ())?;
+    let message: String = unescape_json_string(captures.get(2).ok_or(())?.as_str())?;
+
+    Ok((code, message))
+}
+
+// Resolves the escape sequences of a JSON string, e.g. the quotes around wallet names
+fn unescape_json_string(s: &str) -> Result<String, ()> {
+    let mut output = String::with_capacity(s.len());
+    let mut chars = s.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            output.push(c);
+            continue;
+        }
+
+        match chars.next().ok_or(())? {
+            'b' => output.push('\u{8}'),
+            'f' => output.push('\u{c}'),
+            'n' => output.push('\n'),
+            'r' => output.push('\r'),
+            't' => output.push('\t'),
+            'u' => {
+                let mut unit = parse_hex_unit(&mut chars)?;
+
+                // Characters outside the BMP are encoded as a surrogate pair
+                if (0xD800..0xDC00).contains(&unit) {
+                    if chars.next() != Some('\\') || chars.next() != Some('u') {
+                        return Err(());
+                    }
+                    let low = parse_hex_unit(&mut chars)?;
+                    if !(0xDC00..=0xDFFF).contains(&low) {
+                        return Err(());
+                    }
+                    unit = 0x10000 + ((unit - 0xD800) << 10) + (low - 0xDC00);
+                }
+
+                output.push(char::from_u32(unit).ok_or(())?);
+            }
+            other => output.push(other),
+        }
+    }
+
+    Ok(output)
+}
+
+// The four hex digits of a `\u` escape, `from_str_radix` alone would also take fewer digits or a sign
+fn parse_hex_unit(chars: &mut std::str::Chars) -> Result<u32, ()> {
+    let hex: String = chars.by_ref().take(4).collect();
+    if hex.len() != 4 || !hex.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return Err(());
+    }
+
+    u32::from_str_radix(&hex, 16).map_err(|_| ())
+}
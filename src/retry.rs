@@ -0,0 +1,122 @@
+// Repeats calls to a node as `Error::retry_hint()` suggests, needs the `tokio` feature
+use crate::{Error, RetryClass};
+use std::future::Future;
+use std::hash::BuildHasher;
+use std::time::Duration;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RetryPolicy {
+    // Attempts of a call failing with transient errors, the first one included
+    pub max_attempts: u32,
+    // Backoff of transient errors without a hint, doubled after every attempt
+    pub backoff: Duration,
+    pub max_backoff: Duration,
+    // Fraction of the backoff randomly left out, so clients that failed together do not retry together.
+    // Clamped to [0, 1], a jitter that is not finite is 0.
+    pub jitter: f64,
+    // A warming up node is polled at the pace of its hint until it is ready or this much time passed
+    pub warmup_timeout: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 5,
+            backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(60),
+            jitter: 0.5,
+            warmup_timeout: Duration::from_secs(600),
+        }
+    }
+}
+
+// Reported to the callback after every call
+#[derive(Debug)]
+pub struct Attempt<'a> {
+    // The first call is attempt 1
+    pub number: u32,
+    // None if the call succeeded
+    pub error: Option<&'a Error>,
+    // How long until the next attempt, None if there is none
+    pub delay: Option<Duration>,
+}
+
+impl RetryPolicy {
+    pub fn with_jitter(self, jitter: f64) -> Self {
+        RetryPolicy { jitter: sanitize_jitter(jitter), ..self }
+    }
+
+    // Calls until it succeeds or fails with an error that is not retried (anymore), which is
+    // returned. Permanent errors, errors needing an operator and idempotent successes end it
+    // right away, the caller decides what an idempotent success means for the call.
+    pub async fn run<T, C, F, R>(&self, mut call: C, mut on_attempt: R) -> Result<T, Error>
+    where
+        C: FnMut() -> F,
+        F: Future<Output = Result<T, Error>>,
+        R: FnMut(&Attempt<'_>),
+    {
+        let start = tokio::time::Instant::now();
+        let mut number = 0;
+        // Transient failures other than warmup
+        let mut transient = 0;
+
+        loop {
+            number += 1;
+            let error = match call().await {
+                Ok(value) => {
+                    on_attempt(&Attempt { number, error: None, delay: None });
+                    return Ok(value);
+                }
+                Err(error) => error,
+            };
+
+            let delay = self.delay(&error, &mut transient, start.elapsed());
+            on_attempt(&Attempt { number, error: Some(&error), delay });
+            match delay {
+                Some(delay) => tokio::time::sleep(delay).await,
+                None => return Err(error),
+            }
+        }
+    }
+
+    pub(crate) fn delay(&self, error: &Error, transient: &mut u32, elapsed: Duration) -> Option<Duration> {
+        let hint = error.retry_hint();
+        if hint.class != RetryClass::Transient {
+            return None;
+        }
+        let backoff = hint.backoff.unwrap_or(self.backoff);
+
+        // Warmup ends on its own, there is no point in backing off further
+        if let Error::RPC_IN_WARMUP(_) = error {
+            let ready_by = elapsed.checked_add(backoff)?;
+            return (ready_by <= self.warmup_timeout).then_some(backoff);
+        }
+
+        *transient += 1;
+        if *transient >= self.max_attempts {
+            return None;
+        }
+        let backoff = backoff
+            .saturating_mul(2u32.saturating_pow(*transient - 1))
+            .min(self.max_backoff);
+
+        // The factor is at most 1, `mul_f64` would still panic on a backoff close to `Duration::MAX`
+        let factor = 1.0 - sanitize_jitter(self.jitter) * random_fraction();
+        Some(Duration::try_from_secs_f64(backoff.as_secs_f64() * factor).unwrap_or(backoff))
+    }
+}
+
+// `f64::clamp` keeps NaN, which would make the delay NaN as well
+fn sanitize_jitter(jitter: f64) -> f64 {
+    if jitter.is_finite() {
+        jitter.clamp(0.0, 1.0)
+    } else {
+        0.0
+    }
+}
+
+// A fraction in [0, 1], a fresh `RandomState` is seeded randomly so no RNG is needed for the jitter
+fn random_fraction() -> f64 {
+    let random = std::collections::hash_map::RandomState::new().hash_one(0u8);
+    random as f64 / u64::MAX as f64
+}
@@ -1,4 +1,9 @@
-use crate::{Error, VerifyError};
+use crate::{
+    BlockValidityReason, CoreVersion, DeserializationError, Error, ErrorCategory, InvalidAddressOrKeyError, InvalidParameterError, MiscError, PsbtError, Remediation, RetryClass, RetryHint, ScriptError,
+    TransactionError, TxDecodeReason, TypeError, VariantInfo, VerifyError, VerifyRejectedError, WalletAlreadyExistsError,
+    WalletAlreadyLoadedError, WalletKeypoolRanOutError, WalletNotFoundError, WalletPassphraseIncorrectError, WalletUnlockNeededError,
+    WalletWrongEncStateError,
+};
 use proc_macro_magic::EnumError;
 
 #[derive(Debug, PartialEq, EnumError)]
@@ -20,6 +25,7 @@ enum Enum2 {
     Variant2(String),
 
     // No patterns
+    #[fallback]
     Generic(String),
 }
 
@@ -50,13 +56,13 @@ fn test_pos() {
 #[test]
 fn test_neg() {
     let res1: Result<Enum1, _> = "hello test1 test2".parse();
-    assert_eq!(Err(()), res1);
+    assert_eq!(Err(Enum1ParseError(String::from("hello test1 test2"))), res1);
 
     let res2: Result<Enum1, _> = "The world is big?".parse();
-    assert_eq!(Err(()), res2);
+    assert_eq!(Err(Enum1ParseError(String::from("The world is big?"))), res2);
 
     let res3: Result<Enum1, _> = "bla blu blub".parse();
-    assert_eq!(Err(()), res3);
+    assert_eq!(res3.unwrap_err().to_string(), "unknown Enum1 message: bla blu blub");
 }
 
 #[test]
@@ -77,6 +83,296 @@ fn test_generic_neg() {
     assert_eq!(enum2, Enum2::Generic(String::from("")));
 }
 
+#[derive(Debug, PartialEq, EnumError)]
+enum Enum3 {
+    #[patterns(r"^Paid (.*) to (\d+) outputs$")]
+    Variant1(bitcoin::Amount, u32),
+
+    #[patterns("^Paid (.*) to (.*) outputs$")]
+    Variant2(String, String),
+
+    #[patterns("^Offset (.*)$")]
+    Variant3(i64),
+}
+
+#[test]
+fn test_typed_captures() {
+    let enum1: Enum3 = "Paid 0.1 BTC to 3 outputs".parse().unwrap();
+    assert_eq!(enum1, Enum3::Variant1(bitcoin::Amount::from_sat(10_000_000), 3));
+
+    // A failed conversion is not a match
+    let enum2: Enum3 = "Paid 0.1 BTC to many outputs".parse().unwrap();
+    assert_eq!(enum2, Enum3::Variant2(String::from("0.1 BTC"), String::from("many")));
+
+    let enum3: Enum3 = "Offset -42".parse().unwrap();
+    assert_eq!(enum3, Enum3::Variant3(-42));
+
+    let res4: Result<Enum3, _> = "Offset 4.2".parse();
+    assert_eq!(Err(Enum3ParseError(String::from("Offset 4.2"))), res4);
+}
+
+#[derive(Debug, PartialEq, EnumError)]
+enum Enum4 {
+    #[patterns(r"^(\d+) (\d+) (\d+) (\d+) (\d+) (\d+)$")]
+    Variant1(u8, u8, u8, u8, u8, u8),
+
+    #[patterns(r"^(\w+) (\w+) (\w+) (\w+) (\w+) (\w+) (\w+) (\w+)$")]
+    Variant2(String, String, String, String, String, String, String, u32),
+}
+
+#[test]
+fn test_many_captures() {
+    let enum1: Enum4 = "1 2 3 4 5 6".parse().unwrap();
+    assert_eq!(enum1, Enum4::Variant1(1, 2, 3, 4, 5, 6));
+
+    let enum2: Enum4 = "a b c d e f g 8".parse().unwrap();
+    assert_eq!(
+        enum2,
+        Enum4::Variant2(
+            String::from("a"),
+            String::from("b"),
+            String::from("c"),
+            String::from("d"),
+            String::from("e"),
+            String::from("f"),
+            String::from("g"),
+            8
+        )
+    );
+}
+
+#[derive(Debug, PartialEq, EnumError)]
+enum Enum5 {
+    #[patterns(
+        r"^(?P<name>\w+) is (?P<age>\d+) years old$",
+        r"^Aged (?P<age>\d+): (?P<name>\w+)$"
+    )]
+    Variant1 { name: String, age: u32 },
+
+    #[patterns(r"^Type (?P<type>\w+)$")]
+    Variant2 { r#type: String },
+}
+
+#[test]
+fn test_named_fields() {
+    let enum1: Enum5 = "Satoshi is 50 years old".parse().unwrap();
+    assert_eq!(enum1, Enum5::Variant1 { name: String::from("Satoshi"), age: 50 });
+
+    let enum2: Enum5 = "Aged 50: Satoshi".parse().unwrap();
+    assert_eq!(enum2, Enum5::Variant1 { name: String::from("Satoshi"), age: 50 });
+
+    let enum3: Enum5 = "Type bech32".parse().unwrap();
+    assert_eq!(enum3, Enum5::Variant2 { r#type: String::from("bech32") });
+}
+
+#[derive(Debug, PartialEq, EnumError)]
+enum Enum6 {
+    #[patterns(r"^Error: (.*?)( \(code (-?\d+)\))?$")]
+    Variant1(String, Option<String>, Option<i32>),
+
+    #[patterns(r"^Warning(?:: (?P<reason>.*))?$")]
+    Variant2 { reason: Option<String> },
+}
+
+#[test]
+fn test_optional_captures() {
+    let enum1: Enum6 = "Error: Disk full (code -5)".parse().unwrap();
+    assert_eq!(
+        enum1,
+        Enum6::Variant1(String::from("Disk full"), Some(String::from(" (code -5)")), Some(-5))
+    );
+
+    let enum2: Enum6 = "Error: Disk full".parse().unwrap();
+    assert_eq!(enum2, Enum6::Variant1(String::from("Disk full"), None, None));
+
+    let enum3: Enum6 = "Warning: Low memory".parse().unwrap();
+    assert_eq!(enum3, Enum6::Variant2 { reason: Some(String::from("Low memory")) });
+
+    let enum4: Enum6 = "Warning".parse().unwrap();
+    assert_eq!(enum4, Enum6::Variant2 { reason: None });
+}
+
+#[derive(Debug, PartialEq, EnumError)]
+enum Enum7 {
+    #[patterns("^Outer (.*)$")]
+    Variant1(Enum2),
+
+    #[patterns("^Outer (.*)$")]
+    Variant2(Enum1),
+}
+
+#[derive(Debug, PartialEq, EnumError)]
+enum Enum8 {
+    #[patterns(r"^Wrapped \((.*)\)$")]
+    Variant1(Enum1),
+
+    #[patterns(r"^Wrapped \((.*)\)$")]
+    Variant2(String),
+}
+
+#[test]
+fn test_nested() {
+    let enum1: Enum7 = "Outer moin moin remix!".parse().unwrap();
+    assert_eq!(enum1, Enum7::Variant1(Enum2::Variant2(String::from("remix"))));
+
+    // Enum2 has a generic variant and always matches
+    let enum2: Enum7 = "Outer two".parse().unwrap();
+    assert_eq!(enum2, Enum7::Variant1(Enum2::Generic(String::from("two"))));
+
+    let enum3: Enum8 = "Wrapped (two)".parse().unwrap();
+    assert_eq!(enum3, Enum8::Variant1(Enum1::Variant2));
+
+    // Enum1 does not match, so the next variant is tried
+    let enum4: Enum8 = "Wrapped (three)".parse().unwrap();
+    assert_eq!(enum4, Enum8::Variant2(String::from("three")));
+}
+
+#[derive(EnumError, Debug, PartialEq)]
+enum Enum9 {
+    #[patterns("^Missing amount for (.*)")]
+    MissingAmount(String),
+
+    #[patterns("^Missing timestamp$")]
+    MissingTimestamp,
+
+    // Declaring it first is a compile error, it would swallow the specific ones
+    #[patterns("^Missing (.*)")]
+    Missing(String),
+}
+
+#[test]
+fn test_specificity() {
+    // The variants are checked in declaration order
+    let enum1: Enum9 = "Missing amount for coins".parse().unwrap();
+    assert_eq!(enum1, Enum9::MissingAmount(String::from("coins")));
+
+    let enum2: Enum9 = "Missing timestamp".parse().unwrap();
+    assert_eq!(enum2, Enum9::MissingTimestamp);
+
+    let enum3: Enum9 = "Missing key".parse().unwrap();
+    assert_eq!(enum3, Enum9::Missing(String::from("key")));
+}
+
+#[derive(EnumError, Debug, PartialEq)]
+enum Enum10 {
+    #[patterns("^moin$")]
+    Variant1,
+
+    #[fallback]
+    Unknown { message: String },
+}
+
+#[derive(EnumError, Debug, PartialEq)]
+enum Enum11 {
+    #[fallback]
+    Other(Box<str>),
+
+    #[patterns("^moin$")]
+    Variant1,
+}
+
+#[derive(EnumError, Debug, PartialEq)]
+enum Enum12 {
+    #[patterns("^moin$")]
+    Variant1,
+
+    #[fallback]
+    Raw(std::borrow::Cow<'static, str>),
+}
+
+#[test]
+fn test_fallback() {
+    let enum1: Enum10 = "moin".parse().unwrap();
+    assert_eq!(enum1, Enum10::Variant1);
+
+    let enum2: Enum10 = "servus".parse().unwrap();
+    assert_eq!(enum2, Enum10::Unknown { message: String::from("servus") });
+    assert!(enum2.is_fallback());
+    assert!(!enum1.is_fallback());
+
+    // The position of the fallback does not matter
+    let enum3: Enum11 = "moin".parse().unwrap();
+    assert_eq!(enum3, Enum11::Variant1);
+
+    let enum4: Enum11 = "servus".parse().unwrap();
+    assert_eq!(enum4, Enum11::Other(Box::from("servus")));
+
+    let enum5: Enum12 = "servus".parse().unwrap();
+    assert_eq!(enum5, Enum12::Raw(std::borrow::Cow::Borrowed("servus")));
+}
+
+#[test]
+fn test_variants() {
+    assert_eq!(
+        Enum1::VARIANTS[0],
+        VariantInfo {
+            name: "Variant1",
+            patterns: &["^Hello (.*) (.*)$", "^(.*) World (.*)!$"],
+            captures: 2,
+            fallback: false,
+            remediation: None,
+            retry: None,
+        }
+    );
+    assert_eq!(Enum1::VARIANTS[1].patterns, &["^two", "regexs$"]);
+
+    // Declaration order, the fallback is listed without patterns
+    let names: Vec<&str> = Enum11::VARIANTS.iter().map(|variant| variant.name).collect();
+    assert_eq!(names, ["Other", "Variant1"]);
+    assert!(Enum11::VARIANTS[0].fallback);
+    assert!(Enum11::VARIANTS[0].patterns.is_empty());
+
+    let enum1: Enum1 = "Hello brave World".parse().unwrap();
+    assert_eq!(enum1.name(), "Variant1");
+    let fields: Vec<String> = enum1.fields().iter().map(|field| format!("{:?}", field)).collect();
+    assert_eq!(fields, [r#""brave""#, r#""World""#]);
+
+    let enum2: Enum10 = "servus".parse().unwrap();
+    assert_eq!(enum2.name(), "Unknown");
+    assert_eq!(enum2.fields().len(), 1);
+}
+
+#[derive(EnumError, Debug, PartialEq)]
+enum Enum13 {
+    #[patterns(until = "25.0", "^Transaction already in block chain$")]
+    #[patterns(since = "25.0", "^Transaction outputs already in utxo set$")]
+    AlreadyInChain,
+
+    // The same wording in releases that do not overlap
+    #[patterns(until = "24.0", "^Fee rate (.*) too high$")]
+    FeeTooHigh(String),
+
+    #[patterns(since = "24.0", "^Fee rate (.*) too high$")]
+    FeeRateTooHigh(String),
+
+    #[fallback]
+    Generic(String),
+}
+
+#[test]
+fn test_versions() {
+    let v23 = CoreVersion::new(23, 0);
+    let v25: CoreVersion = "v25.1.0".parse().unwrap();
+    assert_eq!(v25, CoreVersion::new(25, 1));
+
+    let enum1 = Enum13::parse_for_version("Transaction outputs already in utxo set", v25).unwrap();
+    assert_eq!(enum1, Enum13::AlreadyInChain);
+
+    // The pattern is not tried for older releases
+    let enum2 = Enum13::parse_for_version("Transaction outputs already in utxo set", v23).unwrap();
+    assert_eq!(enum2, Enum13::Generic(String::from("Transaction outputs already in utxo set")));
+
+    let enum3 = Enum13::parse_for_version("Fee rate 1000 too high", v23).unwrap();
+    assert_eq!(enum3, Enum13::FeeTooHigh(String::from("1000")));
+
+    let enum4 = Enum13::parse_for_version("Fee rate 1000 too high", v25).unwrap();
+    assert_eq!(enum4, Enum13::FeeRateTooHigh(String::from("1000")));
+
+    // Without a version every pattern is tried
+    let enum5: Enum13 = "Transaction already in block chain".parse().unwrap();
+    assert_eq!(enum5, Enum13::AlreadyInChain);
+}
+
 // Tests with real erorrs
 #[test]
 fn from_str() {
@@ -92,3 +388,552 @@ fn from_str() {
     let error2: Error = error_str2.parse().unwrap();
     assert_eq!(error2, Error::RPC_VERIFY_ALREADY_IN_CHAIN);
 }
+
+#[test]
+fn wallet_encryption_errors() {
+    let error_str1 = String::from(
+        r#"{"code":-13,"message":"Error: Please enter the wallet passphrase with walletpassphrase first."}"#
+    );
+    let error1: Error = error_str1.parse().unwrap();
+    assert_eq!(error1, Error::RPC_WALLET_UNLOCK_NEEDED(WalletUnlockNeededError::PassphraseRequired));
+
+    let error_str2 = String::from(
+        r#"{"code":-15,"message":"Error: running with an unencrypted wallet, but walletpassphrase was called."}"#
+    );
+    let error2: Error = error_str2.parse().unwrap();
+    assert_eq!(
+        error2,
+        Error::RPC_WALLET_WRONG_ENC_STATE(WalletWrongEncStateError::NotEncrypted(String::from("walletpassphrase")))
+    );
+}
+
+#[test]
+fn multi_wallet_errors() {
+    let error_str1 = String::from(
+        r#"{"code":-35,"message":"Wallet \"alice\" is already loaded."}"#
+    );
+    let error1: Error = error_str1.parse().unwrap();
+    assert_eq!(
+        error1,
+        Error::RPC_WALLET_ALREADY_LOADED(WalletAlreadyLoadedError::AlreadyLoaded(String::from("alice")))
+    );
+
+    let error_str2 = String::from(
+        r#"createwallet RPC error: {"code":-36,"message":"Wallet file verification failed. Failed to create database path '/home/bob/.bitcoin/wallets/bob'. Database already exists."}"#
+    );
+    let error2: Error = error_str2.parse().unwrap();
+    assert_eq!(
+        error2,
+        Error::RPC_WALLET_ALREADY_EXISTS(WalletAlreadyExistsError::DatabaseAlreadyExists(
+            String::from("/home/bob/.bitcoin/wallets/bob")
+        ))
+    );
+}
+
+#[test]
+fn json_escapes() {
+    let parse = |message: &str| format!(r#"{{"code":-42,"message":"{}"}}"#, message).parse::<Error>();
+    let unknown = |message: &str| Ok(Error::RPC_UNKOWN_ERROR(-42, String::from(message)));
+
+    assert_eq!(parse(r#"Wallet \"caf\u00e9\" is already loaded."#), unknown(r#"Wallet "café" is already loaded."#));
+    assert_eq!(parse(r"\ud83d\ude00"), unknown("😀"));
+
+    // Fewer than four digits
+    assert_eq!(parse(r"\u41"), Err(()));
+    // A sign is not a digit
+    assert_eq!(parse(r"\u+041"), Err(()));
+    // The high surrogate is not followed by a low one
+    assert_eq!(parse(r"\ud83d"), Err(()));
+    assert_eq!(parse(r"\ud83d\u0041"), Err(()));
+    assert_eq!(parse(r"\ud83d\ud83d"), Err(()));
+    // A low surrogate on its own
+    assert_eq!(parse(r"\ude00"), Err(()));
+}
+
+#[test]
+fn keypool_errors() {
+    let error_str1 = String::from(
+        r#"{"code":-12,"message":"Error: Keypool ran out, please call keypoolrefill first"}"#
+    );
+    let error1: Error = error_str1.parse().unwrap();
+    assert_eq!(error1, Error::RPC_WALLET_KEYPOOL_RAN_OUT(WalletKeypoolRanOutError::KeypoolRanOut));
+
+    let error_str2 = String::from(
+        r#"{"code":-12,"message":"Error: No bech32m addresses available."}"#
+    );
+    let error2: Error = error_str2.parse().unwrap();
+    match error2 {
+        Error::RPC_WALLET_KEYPOOL_RAN_OUT(e) => {
+            assert_eq!(e, WalletKeypoolRanOutError::NoDescriptorForOutputType(String::from("bech32m")));
+            assert!(e.is_missing_descriptor());
+        }
+        _ => panic!("Unexpected error {:?}", error2),
+    }
+}
+
+#[test]
+fn psbt_errors() {
+    let error_str1 = String::from(
+        r#"{"code":-25,"message":"External signer not found"}"#
+    );
+    let error1: Error = error_str1.parse().unwrap();
+    assert_eq!(error1, Error::RPC_VERIFY_ERROR(VerifyError::Generic(String::from("External signer not found"))));
+    match error1 {
+        Error::RPC_VERIFY_ERROR(e) => assert_eq!(e.psbt_error(), Some(PsbtError::ExternalSignerNotFound)),
+        _ => panic!("Unexpected error {:?}", error1),
+    }
+
+    let error_str2 = String::from(
+        r#"{"code":-22,"message":"Specified sighash value does not match value stored in PSBT"}"#
+    );
+    let error2: Error = error_str2.parse().unwrap();
+    match error2 {
+        Error::RPC_DESERIALIZATION_ERROR(e) => assert_eq!(e.psbt_error(), Some(PsbtError::SighashMismatch)),
+        _ => panic!("Unexpected error {:?}", error2),
+    }
+
+    let res3: Result<PsbtError, _> = "Block not found".parse();
+    assert_eq!(res3.unwrap_err().message(), "Block not found");
+}
+
+#[test]
+fn transaction_errors() {
+    let error_str1 = String::from(
+        r#"{"code":-25,"message":"Fee exceeds maximum configured by user (e.g. -maxtxfee, maxfeerate)"}"#
+    );
+    let error1: Error = error_str1.parse().unwrap();
+    assert_eq!(error1.transaction_error(), Some(TransactionError::MaxFeeExceeded));
+
+    let error_str2 = String::from(
+        r#"{"code":-1,"message":"Fee exceeds maximum configured by user (e.g. -maxtxfee, maxfeerate)"}"#
+    );
+    let error2: Error = error_str2.parse().unwrap();
+    assert_eq!(error2.transaction_error(), Some(TransactionError::MaxFeeExceeded));
+
+    let error_str3 = String::from(
+        r#"{"code":-27,"message":"Transaction already in block chain"}"#
+    );
+    let error3: Error = error_str3.parse().unwrap();
+    assert_eq!(error3.transaction_error(), Some(TransactionError::AlreadyInChain));
+
+    // Codes Core picks for single transaction errors
+    let error4 = Error::from_code(-8, "PSBT is not well-formed");
+    assert_eq!(error4.transaction_error(), Some(TransactionError::InvalidPsbt));
+    let error5 = Error::from_code(-8, "PSBTs not compatible (different transactions)");
+    assert_eq!(error5.transaction_error(), Some(TransactionError::PsbtMismatch));
+    let error6 = Error::from_code(-22, "Specified sighash value does not match value stored in PSBT");
+    assert_eq!(error6.transaction_error(), Some(TransactionError::SighashMismatch));
+    let error7 = Error::from_code(-31, "Peer-to-peer functionality missing or disabled");
+    assert_eq!(error7.transaction_error(), Some(TransactionError::P2pDisabled));
+    assert_eq!(Error::from_code(-8, "Block height out of range").transaction_error(), None);
+
+    // The same error in the wording of the release
+    let message = "Transaction outputs already in utxo set";
+    assert_eq!(TransactionError::parse_for_version(message, CoreVersion::new(28, 0)), Ok(TransactionError::AlreadyInChain));
+    assert!(TransactionError::parse_for_version(message, CoreVersion::new(27, 0)).is_err());
+    let message = "Transaction already in block chain";
+    assert_eq!(TransactionError::parse_for_version(message, CoreVersion::new(27, 0)), Ok(TransactionError::AlreadyInChain));
+    assert!(TransactionError::parse_for_version(message, CoreVersion::new(28, 0)).is_err());
+}
+
+#[test]
+fn typed_errors() {
+    let error_str1 = String::from(
+        r#"{"code":-25,"message":"Must submit previous header (000000000000000000024bead8df69990852c202db0e0097c1a12ea637d7e96d) first"}"#
+    );
+    let error1: Error = error_str1.parse().unwrap();
+    assert_eq!(
+        error1,
+        Error::RPC_VERIFY_ERROR(VerifyError::PreviousHeaderMissing(
+            "000000000000000000024bead8df69990852c202db0e0097c1a12ea637d7e96d".parse().unwrap()
+        ))
+    );
+
+    let error_str2 = String::from(
+        r#"{"code":-1,"message":"Rescan failed for key with creation timestamp 1687000000. There was an error reading a block from time 1687000100, which is after or within 7200 seconds of key creation, and could contain transactions pertaining to the key. As a result, transactions and coins using this key may not appear in the wallet. This error could be caused by pruning or data corruption (see bitcoind log for details) and could be dealt with by downloading and rescanning the relevant blocks (see -reindex option and rescanblockchain RPC)."}"#
+    );
+    let error2: Error = error_str2.parse().unwrap();
+    assert_eq!(
+        error2,
+        Error::RPC_MISC_ERROR(MiscError::RescanFailed {
+            timestamp: 1687000000,
+            block_time: 1687000100,
+            window: 7200
+        })
+    );
+}
+
+#[test]
+fn nested_errors() {
+    let error_str1 = String::from(
+        r#"{"code":-25,"message":"TestBlockValidity failed: bad-cb-amount, coinbase pays too much (actual=5000000001 vs limit=5000000000)"}"#
+    );
+    let error1: Error = error_str1.parse().unwrap();
+    assert_eq!(
+        error1,
+        Error::RPC_VERIFY_ERROR(VerifyError::BlockValidityFailed(BlockValidityReason::CoinbasePaysTooMuch {
+            actual: 5000000001,
+            limit: 5000000000
+        }))
+    );
+
+    let error_str2 = String::from(
+        r#"{"code":-22,"message":"TX decode failed invalid base64"}"#
+    );
+    let error2: Error = error_str2.parse().unwrap();
+    assert_eq!(
+        error2,
+        Error::RPC_DESERIALIZATION_ERROR(DeserializationError::TxDecodeFailed(TxDecodeReason::InvalidBase64))
+    );
+
+    let error_str3 = String::from(
+        r#"{"code":-26,"message":"mandatory-script-verify-flag-failed (Signature must be zero for failed CHECK(MULTI)SIG operation)"}"#
+    );
+    let error3: Error = error_str3.parse().unwrap();
+    assert_eq!(
+        error3,
+        Error::RPC_VERIFY_REJECTED(VerifyRejectedError::MandatoryScriptVerifyFlagFailed(ScriptError::SigNullFail))
+    );
+}
+
+#[test]
+fn error_variants() {
+    let verify = Error::VARIANTS.iter().find(|info| info.code == -25).unwrap();
+    assert_eq!(verify.name, "RPC_VERIFY_ERROR");
+    assert_eq!(verify.enum_name, Some("VerifyError"));
+    assert!(verify.variants.iter().any(|variant| variant.name == "MissingOrSpend"));
+
+    // Every code is listed once
+    let mut codes: Vec<i32> = Error::VARIANTS.iter().map(|info| info.code).collect();
+    codes.sort_unstable();
+    codes.dedup();
+    assert_eq!(codes.len(), Error::VARIANTS.len());
+
+    // The message of a unit variant is ignored
+    let in_chain = Error::VARIANTS.iter().find(|info| info.code == -27).unwrap();
+    assert_eq!(in_chain.enum_name, None);
+    assert!(in_chain.variants.is_empty());
+}
+
+#[test]
+fn error_codes() {
+    let error_str1 = String::from(r#"{"code":-33,"message":"Mempool disabled or instance not found"}"#);
+    let error1: Error = error_str1.parse().unwrap();
+    assert_eq!(error1, Error::RPC_CLIENT_MEMPOOL_DISABLED);
+    assert_eq!(error1.code(), -33);
+
+    let error_str2 = String::from(r#"{"code":-5,"message":"Block not found"}"#);
+    let error2: Error = error_str2.parse().unwrap();
+    assert_eq!(error2, Error::RPC_INVALID_ADDRESS_OR_KEY(InvalidAddressOrKeyError::BlockNotFound));
+
+    let error_str3 = String::from(r#"{"code":-8,"message":"Unknown named parameter foo"}"#);
+    let error3: Error = error_str3.parse().unwrap();
+    assert_eq!(error3, Error::RPC_INVALID_PARAMETER(InvalidParameterError::UnknownNamedParameter(String::from("foo"))));
+    assert_eq!(error3.code(), -8);
+
+    assert!(!error3.is_fallback());
+    assert!(Error::from_code(-8, "Something new").is_fallback());
+
+    // A known code keeps its variant even if no pattern matches
+    let error = Error::from_code(-3, "Something new");
+    assert_eq!(error, Error::RPC_TYPE_ERROR(TypeError::Generic(String::from("Something new"))));
+    assert_eq!(error.name(), "RPC_TYPE_ERROR");
+    assert_eq!(error.category(), ErrorCategory::General);
+    assert!(error.is_fallback());
+
+    // Unknown codes keep their code
+    let error4 = Error::from_code(-42, "Something new");
+    assert_eq!(error4, Error::RPC_UNKOWN_ERROR(-42, String::from("Something new")));
+    assert_eq!(error4.code(), -42);
+
+    assert_eq!(error3.name(), "RPC_INVALID_PARAMETER");
+    assert_eq!(error3.category(), ErrorCategory::General);
+    let (variant, fields) = error3.message_variant();
+    assert_eq!(variant, Some("UnknownNamedParameter"));
+    assert_eq!(format!("{:?}", fields), r#"["foo"]"#);
+
+    assert_eq!(error1.name(), "RPC_CLIENT_MEMPOOL_DISABLED");
+    assert_eq!(error1.category(), ErrorCategory::Chain);
+    assert_eq!(error1.message_variant().0, None);
+
+    assert_eq!(error4.name(), "RPC_UNKOWN_ERROR");
+    assert_eq!(error4.category(), ErrorCategory::Unknown);
+    assert_eq!(format!("{:?}", error4.message_variant().1), r#"[-42, "Something new"]"#);
+
+    // Every listed code parses back into its variant and category
+    for info in Error::VARIANTS {
+        assert_eq!(Error::from_code(info.code, "").code(), info.code);
+        assert_eq!(Error::from_code(info.code, "").category(), info.category);
+    }
+    let category = |code| Error::VARIANTS.iter().find(|info| info.code == code).unwrap().category;
+    assert_eq!(category(-13), ErrorCategory::Wallet);
+    assert_eq!(category(-9), ErrorCategory::P2pClient);
+}
+
+#[derive(EnumError, Debug, PartialEq)]
+enum Enum14 {
+    #[patterns("^Keypool ran out$")]
+    #[remediation(rpc = "keypoolrefill", docs = "https://bitcoincore.org/en/doc/25.0.0/rpc/wallet/keypoolrefill/")]
+    Variant1,
+    #[patterns("^Index (.*) is not enabled$")]
+    #[remediation(flag = "-blockfilterindex")]
+    Variant2(String),
+    #[fallback]
+    Generic(String),
+}
+
+#[test]
+fn test_remediation() {
+    let enum1: Enum14 = "Keypool ran out".parse().unwrap();
+    assert_eq!(
+        enum1.remediation(),
+        Some(Remediation {
+            rpc: Some("keypoolrefill"),
+            flag: None,
+            docs: Some("https://bitcoincore.org/en/doc/25.0.0/rpc/wallet/keypoolrefill/"),
+        })
+    );
+
+    let enum2: Enum14 = "Index basic is not enabled".parse().unwrap();
+    assert_eq!(enum2.remediation().unwrap().flag, Some("-blockfilterindex"));
+    assert_eq!(Enum14::VARIANTS[1].remediation, enum2.remediation());
+
+    let enum3: Enum14 = "servus".parse().unwrap();
+    assert_eq!(enum3.remediation(), None);
+}
+
+#[test]
+fn error_remediation() {
+    let error = Error::from_code(-12, "Error: Keypool ran out, please call keypoolrefill first");
+    assert_eq!(error.remediation().unwrap().rpc, Some("keypoolrefill"));
+
+    let error = Error::from_code(-1, "Index is not enabled for filtertype basic");
+    assert_eq!(error.remediation().unwrap().flag, Some("-blockfilterindex"));
+
+    let error = Error::from_code(-18, "Requested wallet does not exist or is not loaded");
+    assert_eq!(error, Error::RPC_WALLET_NOT_FOUND(WalletNotFoundError::NotLoaded));
+    assert_eq!(error.remediation().unwrap().rpc, Some("loadwallet"));
+
+    // Only fetching a block the node has never seen needs more than getblockfrompeer
+    let error = Error::from_code(-1, "In prune mode, only blocks that the node has already synced previously can be fetched from a peer");
+    assert_eq!(error, Error::RPC_MISC_ERROR(MiscError::BlockNotSyncedPrunedNode));
+    assert_eq!(error.remediation(), Some(Remediation { rpc: None, flag: Some("-prune=0"), docs: None }));
+    let error = Error::from_code(-1, "Block not available (pruned data)");
+    assert_eq!(error.remediation().unwrap().rpc, Some("getblockfrompeer"));
+
+    // The remediation of the code covers messages no pattern matched
+    let error = Error::from_code(-13, "Something new");
+    assert!(error.is_fallback());
+    assert_eq!(error.remediation().unwrap().rpc, Some("walletpassphrase"));
+    let info = Error::VARIANTS.iter().find(|info| info.code == -13).unwrap();
+    assert_eq!(info.remediation, error.remediation());
+    // and the variants that do not declare their own
+    let error = Error::from_code(-13, "Error: Please enter the wallet passphrase with walletpassphrase first.");
+    assert_eq!(WalletUnlockNeededError::PassphraseRequired.remediation(), None);
+    assert_eq!(error.remediation(), info.remediation);
+
+    // Unit variants, unknown messages of codes without a remediation and unknown codes have none
+    assert_eq!(Error::from_code(-27, "").remediation(), None);
+    assert_eq!(Error::from_code(-12, "Something new").remediation(), None);
+    assert_eq!(Error::from_code(-42, "Something new").remediation(), None);
+}
+
+#[derive(EnumError, Debug, PartialEq)]
+enum Enum15 {
+    #[patterns("^Still indexing$")]
+    #[retry(transient, backoff_ms = 250)]
+    Variant1,
+    #[patterns("^Already done$")]
+    #[retry(idempotent_success)]
+    Variant2,
+    #[fallback]
+    Generic(String),
+}
+
+#[test]
+fn test_retry_hint() {
+    let enum1: Enum15 = "Still indexing".parse().unwrap();
+    assert_eq!(
+        enum1.retry_hint(),
+        Some(RetryHint { class: RetryClass::Transient, backoff: Some(std::time::Duration::from_millis(250)) })
+    );
+    assert_eq!(Enum15::VARIANTS[0].retry, enum1.retry_hint());
+
+    let enum2: Enum15 = "Already done".parse().unwrap();
+    assert_eq!(enum2.retry_hint(), Some(RetryHint { class: RetryClass::IdempotentSuccess, backoff: None }));
+
+    let enum3: Enum15 = "servus".parse().unwrap();
+    assert_eq!(enum3.retry_hint(), None);
+}
+
+#[test]
+fn error_retry_class() {
+    // The code decides if the variant of the message has no hint
+    let error = Error::from_code(-28, "Loading block index…");
+    assert_eq!(error.retry_class(), RetryClass::Transient);
+    assert_eq!(error.retry_hint().backoff, Some(std::time::Duration::from_secs(1)));
+    assert_eq!(Error::from_code(-9, "").retry_class(), RetryClass::Transient);
+    assert_eq!(Error::from_code(-10, "").retry_class(), RetryClass::Transient);
+    assert_eq!(Error::from_code(-8, "Something new").retry_class(), RetryClass::Permanent);
+    assert_eq!(Error::from_code(-22, "TX decode failed").retry_class(), RetryClass::Permanent);
+    assert_eq!(Error::from_code(-27, "").retry_class(), RetryClass::IdempotentSuccess);
+    assert_eq!(Error::from_code(-13, "Something new").retry_class(), RetryClass::NeedsOperator);
+
+    // The variant overrides the code
+    let error = Error::from_code(-1, "Filter not found. Block filters are still in the process of being indexed.");
+    assert_eq!(error, Error::RPC_MISC_ERROR(MiscError::IndexNotReady));
+    assert_eq!(error.retry_class(), RetryClass::Transient);
+    let error = Error::from_code(-1, "The mempool was not loaded yet");
+    assert_eq!(error, Error::RPC_MISC_ERROR(MiscError::MempoolNotLoaded));
+    assert_eq!(error.retry_hint().backoff, Some(std::time::Duration::from_secs(5)));
+    let error = Error::from_code(-1, "Index is not enabled for filtertype basic");
+    assert_eq!(error.retry_class(), RetryClass::NeedsOperator);
+    let error = Error::from_code(-35, r#"Wallet "alice" is already loaded."#);
+    assert_eq!(error.retry_class(), RetryClass::IdempotentSuccess);
+    let error = Error::from_code(-35, "Refusing to load database. Data file '/tmp/alice' is already loaded.");
+    assert_eq!(error.retry_class(), RetryClass::NeedsOperator);
+
+    // Without a hint an error is permanent
+    assert_eq!(Error::from_code(-1, "Something new").retry_hint(), RetryHint::PERMANENT);
+    assert_eq!(Error::from_code(-42, "Something new").retry_hint(), RetryHint::PERMANENT);
+
+    let info = Error::VARIANTS.iter().find(|info| info.code == -28).unwrap();
+    assert_eq!(info.retry.map(|hint| hint.class), Some(RetryClass::Transient));
+
+    let hint = RetryHint::for_http_response(503, "Work queue depth exceeded").unwrap();
+    assert_eq!(hint.class, RetryClass::Transient);
+    assert_eq!(RetryHint::for_http_response(500, "Work queue depth exceeded"), None);
+    assert_eq!(RetryHint::for_http_response(503, "Service unavailable"), None);
+}
+
+#[test]
+fn error_versions() {
+    let error_str = String::from(r#"{"code":-25,"message":"Input not found or already spent"}"#);
+    let error = Error::parse_for_version(&error_str, CoreVersion::new(25, 0)).unwrap();
+    assert_eq!(error, Error::RPC_VERIFY_ERROR(VerifyError::MissingOrSpend));
+
+    // The reason was added in v25.0, older releases cannot have sent it
+    let error_str = String::from(r#"{"code":-14,"message":"The wallet passphrase entered is incorrect. It contains a null character (ie - a zero byte)."}"#);
+    let error = Error::parse_for_version(&error_str, CoreVersion::new(25, 0)).unwrap();
+    assert_eq!(error, Error::RPC_WALLET_PASSPHRASE_INCORRECT(WalletPassphraseIncorrectError::ContainsNullCharacter));
+    assert!(!error.is_fallback());
+    let error = Error::parse_for_version(&error_str, CoreVersion::new(24, 0)).unwrap();
+    assert!(matches!(error, Error::RPC_WALLET_PASSPHRASE_INCORRECT(WalletPassphraseIncorrectError::Generic(_))));
+    assert!(error.is_fallback());
+
+    let message = "Unspendable output exceeds maximum configured by user (maxburnamount)";
+    assert_eq!(TransactionError::parse_for_version(message, CoreVersion::new(25, 0)), Ok(TransactionError::MaxBurnExceeded));
+    assert!(TransactionError::parse_for_version(message, CoreVersion::new(24, 0)).is_err());
+}
+
+// A node that is warming up for the first `warmup` calls and then answers with the number of the call
+#[cfg(feature = "tokio")]
+fn fake_node(calls: &std::cell::Cell<u32>, warmup: u32) -> impl std::future::Future<Output = Result<u32, Error>> {
+    calls.set(calls.get() + 1);
+    let call = calls.get();
+    async move {
+        if call <= warmup {
+            return Err(Error::from_code(-28, "Loading block index…"));
+        }
+        Ok(call)
+    }
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test(start_paused = true)]
+async fn retry_warmup() {
+    use crate::RetryPolicy;
+    use std::time::Duration;
+
+    let calls = std::cell::Cell::new(0);
+    let mut attempts = Vec::new();
+    let start = tokio::time::Instant::now();
+    let result = RetryPolicy::default()
+        .run(
+            || fake_node(&calls, 3),
+            |attempt| attempts.push((attempt.number, attempt.error.map(Error::code), attempt.delay)),
+        )
+        .await;
+
+    assert_eq!(result, Ok(4));
+    // Warmup is polled at the pace of its hint and does not count against `max_attempts`
+    let second = Some(Duration::from_secs(1));
+    assert_eq!(
+        attempts,
+        [(1, Some(-28), second), (2, Some(-28), second), (3, Some(-28), second), (4, None, None)]
+    );
+    assert_eq!(start.elapsed(), Duration::from_secs(3));
+
+    // Until the node took too long
+    let calls = std::cell::Cell::new(0);
+    let policy = RetryPolicy { warmup_timeout: Duration::from_secs(2), ..RetryPolicy::default() };
+    let result = policy.run(|| fake_node(&calls, 10), |_| {}).await;
+    assert_eq!(result.unwrap_err().code(), -28);
+    assert_eq!(calls.get(), 3);
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test(start_paused = true)]
+async fn retry_backoff() {
+    use crate::RetryPolicy;
+    use std::time::Duration;
+
+    // Transient errors back off exponentially up to `max_backoff`
+    let policy = RetryPolicy { max_attempts: 4, max_backoff: Duration::from_secs(15), jitter: 0.0, ..RetryPolicy::default() };
+    let mut delays = Vec::new();
+    let result: Result<(), Error> = policy
+        .run(|| async { Err(Error::from_code(-9, "")) }, |attempt| delays.push(attempt.delay))
+        .await;
+    assert_eq!(result, Err(Error::RPC_CLIENT_NOT_CONNECTED));
+    assert_eq!(
+        delays,
+        [Some(Duration::from_secs(5)), Some(Duration::from_secs(10)), Some(Duration::from_secs(15)), None]
+    );
+
+    // The jitter only shortens the backoff
+    let policy = RetryPolicy { max_attempts: 2, ..RetryPolicy::default() };
+    let mut delay = None;
+    let _ = policy
+        .run(|| async { Err::<(), _>(Error::from_code(-9, "")) }, |attempt| delay = delay.or(attempt.delay))
+        .await;
+    let delay = delay.unwrap();
+    assert!(delay >= Duration::from_millis(2500) && delay <= Duration::from_secs(5), "{:?}", delay);
+
+    // A jitter that is not a number is left out instead of making the delay NaN
+    let policy = RetryPolicy { max_attempts: 2, ..RetryPolicy::default() }.with_jitter(f64::NAN);
+    assert_eq!(policy.jitter, 0.0);
+    let policy = RetryPolicy { jitter: f64::NAN, ..policy };
+    let mut delays = Vec::new();
+    let _ = policy
+        .run(|| async { Err::<(), _>(Error::from_code(-9, "")) }, |attempt| delays.push(attempt.delay))
+        .await;
+    assert_eq!(delays, [Some(Duration::from_secs(5)), None]);
+    assert_eq!(RetryPolicy::default().with_jitter(f64::INFINITY).jitter, 0.0);
+    assert_eq!(RetryPolicy::default().with_jitter(2.0).jitter, 1.0);
+
+    // Huge limits saturate instead of overflowing
+    let policy = RetryPolicy { max_attempts: u32::MAX, max_backoff: Duration::MAX, ..RetryPolicy::default() };
+    let mut transient = 100;
+    let delay = policy.delay(&Error::from_code(-10, ""), &mut transient, Duration::ZERO).unwrap();
+    assert!(delay <= Duration::from_secs(60).saturating_mul(u32::MAX), "{:?}", delay);
+    let policy = RetryPolicy { warmup_timeout: Duration::MAX, ..policy };
+    let warmup = Error::from_code(-28, "Loading block index…");
+    assert_eq!(policy.delay(&warmup, &mut 0, Duration::MAX), None);
+    assert_eq!(policy.delay(&warmup, &mut 0, Duration::from_secs(60)), Some(Duration::from_secs(1)));
+
+    // Everything else ends it right away
+    for (code, message) in [(-8, "Unknown named parameter foo"), (-13, ""), (-27, ""), (-42, "Something new")] {
+        let calls = std::cell::Cell::new(0);
+        let result: Result<(), Error> = RetryPolicy::default()
+            .run(
+                || {
+                    calls.set(calls.get() + 1);
+                    async move { Err(Error::from_code(code, message)) }
+                },
+                |attempt| assert_eq!(attempt.delay, None),
+            )
+            .await;
+        assert_eq!(result.unwrap_err().code(), code);
+        assert_eq!(calls.get(), 1);
+    }
+}
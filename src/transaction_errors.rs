@@ -0,0 +1,121 @@
+use proc_macro_magic::EnumError;
+
+// Messages of Core's PSBT errors. They are not bound to a single code and are
+// returned as -22, -25 or -4 depending on the RPC.
+// https://github.com/bitcoin/bitcoin/blob/v25.0/src/util/error.cpp
+#[derive(Clone, Debug, PartialEq, EnumError)]
+pub enum PsbtError {
+    #[patterns("^Inputs missing or spent$")]
+    MissingInputs,
+
+    #[patterns("^PSBT is not well-formed$")]
+    Invalid,
+
+    #[patterns(r"^PSBTs not compatible \(different transactions\)$")]
+    Mismatch,
+
+    #[patterns("^PSBT is not finalized$")]
+    NotFinalized,
+
+    #[patterns("^Specified sighash value does not match value stored in PSBT$")]
+    SighashMismatch,
+
+    // Not a TransactionError in v25.0, later releases report it as a PSBTError
+    #[patterns("^Input needs additional signatures or other data$")]
+    Incomplete,
+
+    #[patterns("^External signer not found$")]
+    ExternalSignerNotFound,
+
+    #[patterns("^External signer failed to sign$")]
+    ExternalSignerFailed,
+
+    // Contains the error reported by the signer
+    // https://github.com/bitcoin/bitcoin/blob/v25.0/src/external_signer.cpp
+    #[patterns("^Signer returned error: (.*)")]
+    SignerReturnedError(String),
+
+    // Not a TransactionError in v25.0, later releases report it as a PSBTError
+    #[patterns("^Signer does not support PSBT$")]
+    Unsupported,
+}
+
+// Messages of Core's transaction errors, e.g. of a failed broadcast. They are
+// not bound to a single code and are returned as -1, -8, -22, -25, -26, -27 or
+// -31 depending on the error and the RPC.
+// https://github.com/bitcoin/bitcoin/blob/v25.0/src/util/error.cpp
+#[derive(Clone, Debug, PartialEq, EnumError)]
+pub enum TransactionError {
+    #[patterns("^Inputs missing or spent$")]
+    MissingInputs,
+
+    // Reworded in v28.0, the outputs of the transaction are unspent
+    #[patterns(until = "28.0", "^Transaction already in block chain$")]
+    #[patterns(since = "28.0", "^Transaction outputs already in utxo set$")]
+    AlreadyInChain,
+
+    #[patterns("^Peer-to-peer functionality missing or disabled$")]
+    P2pDisabled,
+
+    #[patterns("^Transaction rejected by mempool$")]
+    MempoolRejected,
+
+    #[patterns("^Mempool internal error$")]
+    MempoolError,
+
+    #[patterns("^PSBT is not well-formed$", "^Invalid PSBT$")]
+    InvalidPsbt,
+
+    #[patterns(r"^PSBTs not compatible \(different transactions\)$")]
+    PsbtMismatch,
+
+    #[patterns("^Specified sighash value does not match value stored in PSBT$")]
+    SighashMismatch,
+
+    #[patterns(r"^Fee exceeds maximum configured by user \(e.g. -maxtxfee, maxfeerate\)$")]
+    MaxFeeExceeded,
+
+    // Added with the maxburnamount argument of sendrawtransaction in v25.0
+    #[patterns(since = "25.0", r"^Unspendable output exceeds maximum configured by user \(maxburnamount\)$")]
+    MaxBurnExceeded,
+
+    #[patterns("^External signer not found$")]
+    ExternalSignerNotFound,
+
+    #[patterns("^External signer failed to sign$")]
+    ExternalSignerFailed,
+
+    #[patterns("^Transaction rejected due to invalid package$")]
+    InvalidPackage,
+}
+
+// Reasons why a base64 encoded PSBT could not be decoded. The ones of psbt.h are thrown as
+// std::ios_base::failure whose message gets a suffix like ": iostream error"
+// https://github.com/bitcoin/bitcoin/blob/v25.0/src/psbt.h
+#[derive(Clone, Debug, PartialEq, EnumError)]
+pub enum TxDecodeReason {
+    // https://github.com/bitcoin/bitcoin/blob/v25.0/src/psbt.cpp
+    #[patterns("^invalid base64$")]
+    InvalidBase64,
+
+    #[patterns("^Invalid PSBT magic bytes(?:: .*)?$")]
+    InvalidMagicBytes,
+
+    #[patterns("^Unsupported version number(?:: .*)?$")]
+    UnsupportedVersion,
+
+    #[patterns("^No unsigned transaction was provided(?:: .*)?$")]
+    UnsignedTxMissing,
+
+    #[patterns("^Unsigned tx does not have empty scriptSigs and scriptWitnesses.(?:: .*)?$")]
+    UnsignedTxNotEmpty,
+
+    #[patterns("^Inputs provided does not match the number of inputs in transaction.(?:: .*)?$")]
+    InputCountMismatch,
+
+    #[patterns("^Outputs provided does not match the number of outputs in transaction.(?:: .*)?$")]
+    OutputCountMismatch,
+
+    #[fallback]
+    Generic(String),
+}
@@ -0,0 +1,127 @@
+use proc_macro_magic::EnumError;
+
+// Reject reasons of a block, formatted as "<reason>, <debug message>"
+// https://github.com/bitcoin/bitcoin/blob/v25.0/src/validation.cpp
+#[derive(Clone, Debug, PartialEq, EnumError)]
+pub enum BlockValidityReason {
+    #[patterns("^high-hash(?:, .*)?$")]
+    HighHash,
+
+    #[patterns("^bad-diffbits(?:, .*)?$")]
+    BadDiffBits,
+
+    #[patterns("^time-too-old(?:, .*)?$")]
+    TimeTooOld,
+
+    #[patterns("^time-too-new(?:, .*)?$")]
+    TimeTooNew,
+
+    // Contains the rejected version, e.g. 0x00000001
+    #[patterns(r"^bad-version\((.*)\)(?:, .*)?$")]
+    BadVersion(String),
+
+    #[patterns("^bad-txnmrklroot(?:, .*)?$")]
+    BadMerkleRoot,
+
+    #[patterns("^bad-txns-duplicate(?:, .*)?$")]
+    DuplicateTx,
+
+    #[patterns("^bad-blk-length(?:, .*)?$")]
+    BadLength,
+
+    #[patterns("^bad-blk-weight(?:, .*)?$")]
+    BadWeight,
+
+    #[patterns("^bad-blk-sigops(?:, .*)?$")]
+    TooManySigops,
+
+    #[patterns("^bad-cb-missing(?:, .*)?$")]
+    CoinbaseMissing,
+
+    #[patterns("^bad-cb-multiple(?:, .*)?$")]
+    MultipleCoinbase,
+
+    #[patterns("^bad-cb-height(?:, .*)?$")]
+    BadCoinbaseHeight,
+
+    // Amounts in satoshis
+    #[patterns(r"^bad-cb-amount, coinbase pays too much \(actual=(?P<actual>\d+) vs limit=(?P<limit>\d+)\)$")]
+    CoinbasePaysTooMuch { actual: i64, limit: i64 },
+
+    #[patterns("^bad-txns-nonfinal(?:, .*)?$")]
+    NonFinalTx,
+
+    #[patterns("^bad-witness-nonce-size(?:, .*)?$")]
+    BadWitnessNonceSize,
+
+    #[patterns("^bad-witness-merkle-match(?:, .*)?$")]
+    BadWitnessMerkleRoot,
+
+    #[patterns("^unexpected-witness(?:, .*)?$")]
+    UnexpectedWitness,
+
+    #[patterns("^bad-prevblk(?:, .*)?$")]
+    BadPrevBlock,
+
+    #[patterns("^prev-blk-not-found(?:, .*)?$")]
+    PrevBlockNotFound,
+
+    #[fallback]
+    Generic(String),
+}
+
+// Messages of a failed script verification
+// https://github.com/bitcoin/bitcoin/blob/v25.0/src/script/script_error.cpp
+#[derive(Clone, Debug, PartialEq, EnumError)]
+pub enum ScriptError {
+    #[patterns("^Script evaluated without error but finished with a false/empty top stack element$")]
+    EvalFalse,
+
+    #[patterns("^Script failed an OP_VERIFY operation$")]
+    Verify,
+
+    #[patterns("^Script failed an OP_EQUALVERIFY operation$")]
+    EqualVerify,
+
+    #[patterns("^Script failed an OP_CHECKMULTISIGVERIFY operation$")]
+    CheckMultisigVerify,
+
+    #[patterns("^Script failed an OP_CHECKSIGVERIFY operation$")]
+    CheckSigVerify,
+
+    #[patterns("^Script failed an OP_NUMEQUALVERIFY operation$")]
+    NumEqualVerify,
+
+    #[patterns("^Operation not valid with the current stack size$")]
+    InvalidStackOperation,
+
+    #[patterns("^Non-canonical DER signature$")]
+    SigDer,
+
+    #[patterns("^Non-canonical signature: S value is unnecessarily high$")]
+    SigHighS,
+
+    #[patterns(r"^Signature must be zero for failed CHECK\(MULTI\)SIG operation$")]
+    SigNullFail,
+
+    #[patterns("^Negative locktime$")]
+    NegativeLocktime,
+
+    #[patterns("^Locktime requirement not satisfied$")]
+    UnsatisfiedLocktime,
+
+    #[patterns("^Witness program hash mismatch$")]
+    WitnessProgramMismatch,
+
+    #[patterns("^Witness program was passed an empty witness$")]
+    WitnessProgramWitnessEmpty,
+
+    #[patterns("^Witness program has incorrect length$")]
+    WitnessProgramWrongLength,
+
+    #[patterns("^Invalid Schnorr signature$")]
+    SchnorrSig,
+
+    #[fallback]
+    Generic(String),
+}
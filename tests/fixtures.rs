@@ -0,0 +1,61 @@
+// Runs the corpus of real errors in tests/fixtures, one file per Core release.
+// Every fixture is parsed with the patterns of its release and compared to the
+// expected variant, so a changed message fails here instead of ending up Generic.
+use bitcoin_rpc_errors::{CoreVersion, Error};
+use serde_json::{json, Value};
+use std::{fs, path::Path};
+
+fn check_fixture(version: CoreVersion, fixture: &Value) -> Result<(), String> {
+    let field = |name: &str| fixture.get(name).ok_or_else(|| format!("fixture misses `{}`: {}", name, fixture));
+    let code = field("code")?.as_i64().ok_or("`code` is not a number")?;
+    let message = field("message")?.as_str().ok_or("`message` is not a string")?;
+    let expected = field("expected")?.as_str().ok_or("`expected` is not a string")?;
+    let source = field("source")?.as_str().ok_or("`source` is not a string")?;
+
+    if !source.starts_with("https://github.com/bitcoin/bitcoin/blob/") {
+        return Err(format!("`source` of {:?} does not point to Bitcoin Core: {}", message, source));
+    }
+    // The message has to be taken from the release of the file, down to the line
+    if !source.contains(&format!("/blob/v{}/", version)) {
+        return Err(format!("`source` of {:?} is not of v{}: {}", message, version, source));
+    }
+    if !source.contains("#L") {
+        return Err(format!("`source` of {:?} does not link a line: {}", message, source));
+    }
+
+    let error_str = json!({ "code": code, "message": message }).to_string();
+    let error = Error::parse_for_version(&error_str, version)
+        .map_err(|_| format!("{:?} could not be parsed ({})", message, source))?;
+
+    let actual = format!("{:?}", error);
+    if actual != expected {
+        return Err(format!("{:?}\n    expected: {}\n    actual:   {}\n    source:   {}", message, expected, actual, source));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn fixtures() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    let mut paths: Vec<_> = fs::read_dir(&dir).unwrap().map(|entry| entry.unwrap().path()).collect();
+    paths.sort();
+
+    let mut failures = Vec::new();
+    let mut num_fixtures = 0;
+    for path in paths {
+        // The file name is the release, e.g. v25.0.json
+        let version: CoreVersion = path.file_stem().unwrap().to_str().unwrap().parse().unwrap();
+        let fixtures: Vec<Value> = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+
+        for fixture in &fixtures {
+            num_fixtures += 1;
+            if let Err(failure) = check_fixture(version, fixture) {
+                failures.push(format!("v{}: {}", version, failure));
+            }
+        }
+    }
+
+    assert!(num_fixtures > 0, "no fixtures found in {}", dir.display());
+    assert!(failures.is_empty(), "{} of {} fixtures failed:\n{}", failures.len(), num_fixtures, failures.join("\n"));
+}
@@ -0,0 +1,7 @@
+// Pins down the diagnostics of the derives and that they work outside this crate
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+    t.pass("tests/ui/pass/*.rs");
+}
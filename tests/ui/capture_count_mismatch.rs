@@ -0,0 +1,12 @@
+use proc_macro_magic::EnumError;
+
+#[derive(EnumError)]
+enum CaptureCountMismatch {
+    #[patterns("^JSON value of type (.*) for field (.*) is not of expected type (.*)")]
+    WrongJsonType(String, String),
+
+    #[patterns("^Missing (.*)", "^(.*) is missing for (.*)$")]
+    Missing(String),
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+use proc_macro_magic::{EnumError, ErrorCode};
+
+#[derive(EnumError)]
+enum MethodDeprecated {
+    #[patterns("^dummy first argument must be excluded$")]
+    WrongDummyArgument,
+}
+
+#[allow(non_camel_case_types)]
+#[derive(ErrorCode)]
+enum CodeWithoutFallback {
+    #[code(-32, category = General)]
+    RPC_METHOD_DEPRECATED(MethodDeprecated),
+
+    #[fallback]
+    RPC_UNKOWN_ERROR(i32, String),
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use proc_macro_magic::ErrorCode;
+
+#[allow(non_camel_case_types)]
+#[derive(ErrorCode)]
+enum DuplicateCode {
+    #[code(-27, category = General)]
+    RPC_VERIFY_ALREADY_IN_CHAIN,
+
+    #[code(-27, category = General)]
+    RPC_IN_WARMUP,
+
+    #[fallback]
+    RPC_UNKOWN_ERROR(i32, String),
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use proc_macro_magic::EnumError;
+
+#[derive(EnumError)]
+enum EmptyPatterns {
+    #[patterns()]
+    BlockNotFound,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use proc_macro_magic::EnumError;
+
+#[derive(EnumError)]
+enum FallbackFields {
+    #[patterns("^Wallet file not specified$")]
+    NotSpecified,
+
+    #[fallback]
+    Unknown(String, String),
+}
+
+fn main() {}
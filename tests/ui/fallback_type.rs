@@ -0,0 +1,15 @@
+use proc_macro_magic::EnumError;
+
+#[derive(Debug)]
+struct Message;
+
+#[derive(EnumError)]
+enum FallbackType {
+    #[patterns("^Wallet file not specified$")]
+    NotSpecified,
+
+    #[fallback]
+    Unknown(Message),
+}
+
+fn main() {}
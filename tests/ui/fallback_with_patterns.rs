@@ -0,0 +1,13 @@
+use proc_macro_magic::EnumError;
+
+#[derive(EnumError)]
+enum FallbackWithPatterns {
+    #[patterns("^Wallet file not specified$")]
+    NotSpecified,
+
+    #[fallback]
+    #[patterns("^(.*)$")]
+    Unknown(String),
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use proc_macro_magic::ErrorCode;
+
+#[allow(non_camel_case_types)]
+#[derive(ErrorCode)]
+enum InvalidCategory {
+    #[code(-27)]
+    RPC_VERIFY_ALREADY_IN_CHAIN,
+
+    #[code(-33, category = Mempool)]
+    RPC_CLIENT_MEMPOOL_DISABLED,
+
+    #[fallback]
+    RPC_UNKOWN_ERROR(i32, String),
+}
+
+fn main() {}
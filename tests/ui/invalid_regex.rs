@@ -0,0 +1,12 @@
+use proc_macro_magic::EnumError;
+
+#[derive(EnumError)]
+enum InvalidRegex {
+    #[patterns("^Block (.* not found$")]
+    BlockNotFound(String),
+
+    #[patterns(r"^Transaction \(.*\) not in mempool.$", "^Transaction [a-f0-9+ not found$")]
+    TxNotInMempool,
+}
+
+fn main() {}
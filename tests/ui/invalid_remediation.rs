@@ -0,0 +1,18 @@
+use proc_macro_magic::EnumError;
+
+#[derive(EnumError)]
+enum InvalidRemediation {
+    #[patterns("^Keypool ran out$")]
+    #[remediation(command = "keypoolrefill")]
+    KeypoolRanOut,
+
+    #[patterns("^Please enter the wallet passphrase$")]
+    #[remediation(rpc = "walletpassphrase", rpc = "walletpassphrasechange")]
+    PassphraseRequired,
+
+    #[patterns("^Index is not enabled$")]
+    #[remediation()]
+    IndexNotEnabled,
+}
+
+fn main() {}
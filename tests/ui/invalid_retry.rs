@@ -0,0 +1,26 @@
+use proc_macro_magic::EnumError;
+
+#[derive(EnumError)]
+enum InvalidRetry {
+    #[patterns("^Loading block index$")]
+    #[retry(later)]
+    Warmup,
+
+    #[patterns("^Invalid parameter$")]
+    #[retry(permanent, transient)]
+    InvalidParameter,
+
+    #[patterns("^Keypool ran out$")]
+    #[retry(needs_operator, backoff_ms = 1000)]
+    KeypoolRanOut,
+
+    #[patterns("^Index is not ready$")]
+    #[retry(backoff_ms = 1000)]
+    IndexNotReady,
+
+    #[patterns("^Still downloading$")]
+    #[retry(transient, backoff_ms = 1000, backoff_ms = 2000)]
+    InitialDownload,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use proc_macro_magic::EnumError;
+
+#[derive(EnumError)]
+enum InvalidVersion {
+    #[patterns(since = "twenty-five", "^Wallet file not specified$")]
+    NotSpecified,
+
+    #[patterns(before = "25.0", "^Wallet already loaded$")]
+    AlreadyLoaded,
+
+    #[patterns(since = "26.0", until = "25.0", "^Wallet already exists$")]
+    AlreadyExists,
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use proc_macro_magic::EnumError;
+
+#[derive(EnumError)]
+enum MethodCollision {
+    #[patterns("^Channel (.*) not found$")]
+    ChannelNotFound(String),
+}
+
+// The derive already adds an inherent `name`
+impl MethodCollision {
+    fn name(&self) -> String {
+        String::from("channel")
+    }
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use proc_macro_magic::ErrorCode;
+
+#[allow(non_camel_case_types)]
+#[derive(ErrorCode)]
+enum MissingCode {
+    #[code(-27, category = General)]
+    RPC_VERIFY_ALREADY_IN_CHAIN,
+
+    RPC_CLIENT_MEMPOOL_DISABLED,
+
+    #[fallback]
+    RPC_UNKOWN_ERROR(i32, String),
+}
+
+fn main() {}
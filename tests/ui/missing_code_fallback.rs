@@ -0,0 +1,10 @@
+use proc_macro_magic::ErrorCode;
+
+#[allow(non_camel_case_types)]
+#[derive(ErrorCode)]
+enum MissingCodeFallback {
+    #[code(-27, category = General)]
+    RPC_VERIFY_ALREADY_IN_CHAIN,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use proc_macro_magic::EnumError;
+
+#[derive(EnumError)]
+enum MissingPatterns {
+    #[patterns("^Block not found$")]
+    BlockNotFound,
+
+    TxNotFound,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use proc_macro_magic::EnumError;
+
+#[derive(EnumError)]
+enum MultipleFallbacks {
+    #[fallback]
+    Unknown(String),
+
+    #[fallback]
+    Other(String),
+}
+
+fn main() {}
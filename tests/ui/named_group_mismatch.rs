@@ -0,0 +1,12 @@
+use proc_macro_magic::EnumError;
+
+#[derive(EnumError)]
+enum NamedGroupMismatch {
+    #[patterns(r"^Unknown address type '(?P<addr_type>.*)'$")]
+    UnknownAddressType { address_type: String },
+
+    #[patterns(r"^Rescan failed for key with creation timestamp (?P<timestamp>\d+) at (\d+)$")]
+    RescanFailed { timestamp: i64, block_time: i64 },
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use proc_macro_magic::EnumError;
+
+#[derive(EnumError)]
+enum NonStringPattern {
+    #[patterns("^Block not found$", 42)]
+    BlockNotFound,
+}
+
+fn main() {}
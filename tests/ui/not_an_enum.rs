@@ -0,0 +1,8 @@
+use proc_macro_magic::EnumError;
+
+#[derive(EnumError)]
+struct NotAnEnum {
+    message: String,
+}
+
+fn main() {}
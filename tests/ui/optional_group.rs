@@ -0,0 +1,9 @@
+use proc_macro_magic::EnumError;
+
+#[derive(EnumError)]
+enum OptionalGroup {
+    #[patterns(r"^Error: (.*?)( \(code (-?\d+)\))?$")]
+    WithCode(String, String, Option<i32>),
+}
+
+fn main() {}
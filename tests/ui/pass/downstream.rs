@@ -0,0 +1,23 @@
+// A crate using the derives on its own enums
+use bitcoin_rpc_errors::{RetryClass, VariantInfo};
+use proc_macro_magic::EnumError;
+
+#[derive(Debug, PartialEq, EnumError)]
+enum LightningError {
+    #[patterns("^Channel (.*) not found$")]
+    #[retry(permanent)]
+    ChannelNotFound(String),
+
+    #[fallback]
+    Generic(String),
+}
+
+fn main() {
+    let error: LightningError = "Channel 42 not found".parse().unwrap();
+    assert_eq!(error, LightningError::ChannelNotFound(String::from("42")));
+    assert_eq!(error.name(), "ChannelNotFound");
+    assert_eq!(error.retry_hint().map(|hint| hint.class), Some(RetryClass::Permanent));
+
+    let variants: &[VariantInfo] = LightningError::VARIANTS;
+    assert_eq!(variants.len(), 2);
+}
@@ -0,0 +1,15 @@
+use proc_macro_magic::ErrorCode;
+
+#[allow(non_camel_case_types)]
+#[derive(ErrorCode)]
+enum RemediationFallback {
+    #[remediation(flag = "-listen")]
+    #[code(-9, category = P2pClient)]
+    RPC_CLIENT_NOT_CONNECTED,
+
+    #[remediation(docs = "https://github.com/bitcoin/bitcoin/blob/v25.0/doc/bitcoin-conf.md")]
+    #[fallback]
+    RPC_UNKOWN_ERROR(i32, String),
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use proc_macro_magic::ErrorCode;
+
+#[allow(non_camel_case_types)]
+#[derive(ErrorCode)]
+enum RetryFallback {
+    #[retry(transient, backoff_ms = 1000)]
+    #[code(-9, category = P2pClient)]
+    RPC_CLIENT_NOT_CONNECTED,
+
+    #[retry(transient)]
+    #[fallback]
+    RPC_UNKOWN_ERROR(i32, String),
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use proc_macro_magic::EnumError;
+
+#[derive(EnumError)]
+enum SwallowedPattern {
+    #[patterns("^Missing (.*)")]
+    Missing(String),
+
+    #[patterns("^Missing amount for (.*)")]
+    MissingAmount(String),
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use proc_macro_magic::EnumError;
+
+#[derive(EnumError)]
+enum UnreachablePattern {
+    #[patterns("^Wallet file not specified (.*)$")]
+    NotSpecified(String),
+
+    #[patterns("^Wallet file not specified (.*)$")]
+    NotSpecifiedAgain(String),
+}
+
+fn main() {}
@@ -0,0 +1,34 @@
+// Explains an error of a node: the code name, the variant the message is parsed
+// into, its captured fields and the category of the code.
+//
+// Usage: bitcoin-rpc-errors [--json] [--core-version 25.0] [<error>]
+// Without an argument the error is read from stdin, e.g. `bitcoin-cli getwalletinfo 2>&1 | bitcoin-rpc-errors`
+use bitcoin_rpc_errors_tools::{exit, explain, Args};
+use std::io::Read;
+
+fn main() {
+    let args = Args::from_env(&["json"]).unwrap_or_else(|err| exit(&err));
+    let version = args.option("core-version").map(|version| {
+        version
+            .parse()
+            .unwrap_or_else(|_| exit(&format!("Invalid Core version {}, expected e.g. 25.0", version)))
+    });
+
+    let input = if args.positional.is_empty() {
+        let mut input = String::new();
+        std::io::stdin()
+            .read_to_string(&mut input)
+            .unwrap_or_else(|err| exit(&format!("Can not read stdin: {}", err)));
+        input
+    } else {
+        args.positional.join(" ")
+    };
+
+    let (code, message) = explain::parse_input(&input).unwrap_or_else(|err| exit(&err));
+    let explanation = explain::explain(code, &message, version);
+    if args.flag("json") {
+        println!("{}", explanation.to_json());
+    } else {
+        print!("{}", explanation.to_text());
+    }
+}
@@ -0,0 +1,96 @@
+// Reports which errors expected by Bitcoin Core's functional tests are parsed
+// into a specific variant and which end up in a fallback.
+//
+// Usage: extract-functional-tests <bitcoin-core-dir> --tag v25.0 [--fixtures <file>]
+use bitcoin_rpc_errors_tools::{
+    classify, exit, fixture, functional_tests, github_link, partial_match, to_fixture_json, Args,
+};
+use std::{collections::BTreeMap, path::Path};
+
+const USAGE: &str = "Usage: extract-functional-tests <bitcoin-core-dir> --tag v25.0 [--fixtures <file>]";
+
+fn main() {
+    let args = Args::from_env(&[]).unwrap_or_else(|err| exit(&err));
+    let core_dir = args
+        .positional
+        .first()
+        .unwrap_or_else(|| exit(USAGE));
+    // The fixtures link the checked out release, tests/fixtures.rs rejects any other
+    let tag = args.option("tag").unwrap_or_else(|| exit(USAGE));
+    let version = tag
+        .parse()
+        .unwrap_or_else(|_| exit(&format!("--tag has to be a release like v25.0, not {}", tag)));
+
+    let (errors, skipped) = functional_tests::extract_from_checkout(Path::new(core_dir))
+        .unwrap_or_else(|err| exit(&format!("Can not read the functional tests of {}: {}", core_dir, err)));
+
+    // Every (code, message) pair once, at its first occurrence
+    let mut pairs = BTreeMap::new();
+    for error in &errors {
+        pairs.entry((error.code, error.message.as_str())).or_insert(error);
+    }
+
+    // Code -> (classified, partial, fallback)
+    let mut per_code: BTreeMap<i32, (usize, usize, usize)> = BTreeMap::new();
+    let mut partials = Vec::new();
+    let mut gaps = Vec::new();
+    for (&(code, message), error) in &pairs {
+        let parsed = classify(code, message, Some(version));
+        let counts = per_code.entry(code).or_default();
+        if !parsed.is_fallback() {
+            counts.0 += 1;
+        } else if let Some(variant) = partial_match(code, message) {
+            // Only part of the message, the whole message may well be covered
+            counts.1 += 1;
+            partials.push((error, variant));
+        } else {
+            counts.2 += 1;
+            gaps.push((error, parsed));
+        }
+    }
+
+    println!(
+        "Extracted {} expected errors ({} distinct) from the functional tests, {} calls skipped",
+        errors.len(),
+        pairs.len(),
+        skipped.len()
+    );
+    println!("Classified: {}", pairs.len() - partials.len() - gaps.len());
+    println!("Partial:    {}", partials.len());
+    println!("Fallback:   {}", gaps.len());
+    println!();
+    println!("{:>6} {:>11} {:>8} {:>9}", "code", "classified", "partial", "fallback");
+    for (code, (classified, partial, fallback)) in &per_code {
+        println!("{:>6} {:>11} {:>8} {:>9}", code, classified, partial, fallback);
+    }
+
+    println!();
+    println!("Partial:");
+    for (error, variant) in &partials {
+        println!("{}:{} {} {:?}", error.file, error.line, error.code, error.message);
+        println!("    -> part of {}", variant);
+    }
+
+    println!();
+    println!("Gaps:");
+    for (error, parsed) in &gaps {
+        println!("{}:{} {} {:?}", error.file, error.line, error.code, error.message);
+        println!("    -> {:?}", parsed);
+    }
+
+    println!();
+    println!("Skipped:");
+    for call in &skipped {
+        println!("{}:{} {}", call.file, call.line, call.reason);
+    }
+
+    if let Some(path) = args.option("fixtures") {
+        let fixtures: Vec<_> = gaps
+            .iter()
+            .map(|(error, _)| fixture(error.code, &error.message, &github_link(tag, &error.file, error.line)))
+            .collect();
+        std::fs::write(path, to_fixture_json(&fixtures.into()))
+            .unwrap_or_else(|err| exit(&format!("Can not write {}: {}", path, err)));
+        eprintln!("Wrote {} fixtures to {}", gaps.len(), path);
+    }
+}
@@ -0,0 +1,28 @@
+// Writes the error reference to docs/ and the "State" checklist into the README.
+//
+// Usage: generate-reference [<bitcoin-rpc-errors-dir>]
+use bitcoin_rpc_errors::Error;
+use bitcoin_rpc_errors_tools::{exit, reference, Args};
+use std::{fs, path::Path};
+
+fn main() {
+    let args = Args::from_env(&[]).unwrap_or_else(|err| exit(&err));
+    let crate_dir = Path::new(args.positional.first().map_or(concat!(env!("CARGO_MANIFEST_DIR"), "/.."), |dir| dir.as_str()));
+
+    let comments = reference::read_source_comments(crate_dir)
+        .unwrap_or_else(|err| exit(&format!("Can not read the source of {}: {}", crate_dir.display(), err)));
+
+    let readme_path = crate_dir.join("README.md");
+    let readme = fs::read_to_string(&readme_path).unwrap_or_else(|err| exit(&format!("Can not read README.md: {}", err)));
+    let readme = reference::update_readme(&readme, &reference::checklist(Error::VARIANTS, &comments))
+        .unwrap_or_else(|| exit("README.md has no \"## State\" section"));
+
+    let write = |path: &Path, content: &str| {
+        fs::write(path, content).unwrap_or_else(|err| exit(&format!("Can not write {}: {}", path.display(), err)));
+        eprintln!("Wrote {}", path.display());
+    };
+    let _ = fs::create_dir_all(crate_dir.join("docs"));
+    write(&crate_dir.join("docs/errors.md"), &reference::markdown(Error::VARIANTS, &comments));
+    write(&crate_dir.join("docs/errors.html"), &reference::html(Error::VARIANTS, &comments));
+    write(&readme_path, &readme);
+}
@@ -0,0 +1,109 @@
+// Reports which `JSONRPCError` call sites of Bitcoin Core are covered by a pattern
+// and suggests `#[patterns]` for the ones that end up in a fallback.
+//
+// Usage: scan-call-sites <bitcoin-core-dir> [--tag v25.0] [--code RPC_MISC_ERROR]
+use bitcoin_rpc_errors::Error;
+use bitcoin_rpc_errors_tools::call_sites::{self, to_rust_literal, CallSite};
+use bitcoin_rpc_errors_tools::{classify, exit, github_link, Args};
+use std::{collections::BTreeMap, path::Path};
+
+fn main() {
+    let args = Args::from_env(&[]).unwrap_or_else(|err| exit(&err));
+    let core_dir = args
+        .positional
+        .first()
+        .unwrap_or_else(|| exit("Usage: scan-call-sites <bitcoin-core-dir> [--tag v25.0] [--code RPC_MISC_ERROR]"));
+    let tag = args.option("tag").unwrap_or("master");
+    // Without a release tag every pattern is tried
+    let version = tag.parse().ok();
+
+    let codes = call_sites::read_codes(Path::new(core_dir))
+        .unwrap_or_else(|err| exit(&format!("Can not read src/rpc/protocol.h of {}: {}", core_dir, err)));
+    let (mut sites, skipped) = call_sites::scan_checkout(Path::new(core_dir))
+        .unwrap_or_else(|err| exit(&format!("Can not read the source of {}: {}", core_dir, err)));
+    if let Some(name) = args.option("code") {
+        sites.retain(|site| site.name == name);
+    }
+
+    // Code -> (name, covered, uncovered call sites)
+    let mut per_code: BTreeMap<i32, (&str, usize, Vec<&CallSite>)> = BTreeMap::new();
+    let mut unknown = Vec::new();
+    for site in &sites {
+        let code = match codes.get(&site.name) {
+            Some(code) => *code,
+            None => {
+                unknown.push(site);
+                continue;
+            }
+        };
+
+        // Aliases like RPC_TRANSACTION_ERROR are listed under the name `Error` uses
+        let name = Error::VARIANTS
+            .iter()
+            .find(|info| info.code == code)
+            .map_or(site.name.as_str(), |info| info.name);
+        let entry = per_code.entry(code).or_insert((name, 0, Vec::new()));
+        if classify(code, &site.sample_message(), version).is_fallback() {
+            entry.2.push(site);
+        } else {
+            entry.1 += 1;
+        }
+    }
+
+    let covered: usize = per_code.values().map(|(_, covered, _)| covered).sum();
+    println!("Found {} JSONRPCError call sites, {} calls skipped", sites.len(), skipped.len());
+    println!("Covered:   {}", covered);
+    println!("Uncovered: {}", sites.len() - covered - unknown.len());
+    println!();
+    println!("{:>6}  {:<36} {:>5} {:>8} {:>10}", "code", "name", "sites", "covered", "uncovered");
+    for (code, (name, covered, uncovered)) in &per_code {
+        println!(
+            "{:>6}  {:<36} {:>5} {:>8} {:>10}",
+            code,
+            name,
+            covered + uncovered.len(),
+            covered,
+            uncovered.len()
+        );
+    }
+
+    println!();
+    println!("Suggestions:");
+    for (code, (name, _, uncovered)) in &per_code {
+        if uncovered.is_empty() {
+            continue;
+        }
+
+        println!();
+        println!("{} ({})", name, code);
+        // Call sites with the same message share one suggestion
+        let mut suggestions: BTreeMap<String, Vec<&CallSite>> = BTreeMap::new();
+        for site in uncovered {
+            suggestions.entry(site.to_pattern()).or_default().push(site);
+        }
+        for (pattern, sites) in &suggestions {
+            for site in sites {
+                println!("    // {}", github_link(tag, &site.file, site.line));
+            }
+            println!("    #[patterns({})]", to_rust_literal(pattern));
+            match sites[0].captures() {
+                0 => println!("    Variant,"),
+                captures => println!("    Variant({}),", vec!["String"; captures].join(", ")),
+            }
+        }
+    }
+
+    if !unknown.is_empty() {
+        println!();
+        println!("Not in protocol.h:");
+        for site in &unknown {
+            println!("{}:{} {}", site.file, site.line, site.name);
+        }
+    }
+
+    println!();
+    println!("Skipped:");
+    for call in &skipped {
+        println!("{}:{} {}", call.file, call.line, call.reason);
+    }
+}
@@ -0,0 +1,451 @@
+// Finds the call sites of `JSONRPCError(RPC_..., message)` in the C++ source of
+// Bitcoin Core, both `throw JSONRPCError(..)` and `return JSONRPCError(..)`.
+// Messages built with string literals, `+` and `strprintf` are turned into
+// candidate patterns, anything computed at runtime becomes a wildcard.
+// https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/request.cpp#L60
+use regex::Regex;
+use std::{collections::BTreeMap, fs, io, path::Path};
+
+const CALL: &str = "JSONRPCError(";
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Part {
+    Literal(String),
+    // A `strprintf` placeholder like %d or %i
+    Integer,
+    // %u
+    Unsigned,
+    // %s and every other placeholder
+    Text,
+    // An expression like `request.params[0].get_str()`
+    Dynamic,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CallSite {
+    // Relative to the Core checkout, e.g. src/rpc/blockchain.cpp
+    pub file: String,
+    pub line: usize,
+    // The constant of protocol.h, e.g. RPC_MISC_ERROR
+    pub name: String,
+    pub message: Vec<Part>,
+}
+
+impl CallSite {
+    // The message as a pattern like the ones in general_errors.rs, placeholders are captured
+    pub fn to_pattern(&self) -> String {
+        let mut pattern = String::from("^");
+        for part in &self.message {
+            match part {
+                Part::Literal(literal) => pattern.push_str(&regex::escape(literal)),
+                Part::Integer => pattern.push_str(r"(-?\d+)"),
+                Part::Unsigned => pattern.push_str(r"(\d+)"),
+                Part::Text => pattern.push_str("(.*)"),
+                Part::Dynamic => pattern.push_str(".*"),
+            }
+        }
+        pattern.push('$');
+
+        pattern
+    }
+
+    // A message this call site could produce, to check if a pattern already covers it
+    pub fn sample_message(&self) -> String {
+        self.message
+            .iter()
+            .map(|part| match part {
+                Part::Literal(literal) => literal.as_str(),
+                Part::Integer | Part::Unsigned => "0",
+                Part::Text | Part::Dynamic => "foo",
+            })
+            .collect()
+    }
+
+    pub fn captures(&self) -> usize {
+        self.message
+            .iter()
+            .filter(|part| matches!(part, Part::Integer | Part::Unsigned | Part::Text))
+            .count()
+    }
+}
+
+// A call whose code is not a constant or whose message has no literal at all
+#[derive(Clone, Debug, PartialEq)]
+pub struct SkippedCall {
+    pub file: String,
+    pub line: usize,
+    pub reason: &'static str,
+}
+
+// Reads the codes of src/rpc/protocol.h, including aliases like RPC_TRANSACTION_ERROR
+pub fn read_codes(core_dir: &Path) -> io::Result<BTreeMap<String, i32>> {
+    Ok(parse_codes(&fs::read_to_string(core_dir.join("src/rpc/protocol.h"))?))
+}
+
+pub fn parse_codes(source: &str) -> BTreeMap<String, i32> {
+    let regex = Regex::new(r"\b(RPC_\w+)\s*=\s*(-?\d+|RPC_\w+)").unwrap();
+    let mut codes = BTreeMap::new();
+    for captures in regex.captures_iter(source) {
+        let value = match captures[2].parse() {
+            Ok(code) => Some(code),
+            Err(_) => codes.get(&captures[2]).copied(),
+        };
+        if let Some(code) = value {
+            codes.insert(captures[1].to_string(), code);
+        }
+    }
+
+    codes
+}
+
+// Scans all C++ files below src/ of the Core checkout at `core_dir`
+pub fn scan_checkout(core_dir: &Path) -> io::Result<(Vec<CallSite>, Vec<SkippedCall>)> {
+    let mut paths = Vec::new();
+    collect_sources(&core_dir.join("src"), &mut paths)?;
+    paths.sort();
+
+    let mut sites = Vec::new();
+    let mut skipped = Vec::new();
+    for path in paths {
+        let file = path.strip_prefix(core_dir).unwrap_or(&path).to_string_lossy().to_string();
+        let (file_sites, file_skipped) = scan_source(&file, &fs::read_to_string(&path)?);
+        sites.extend(file_sites);
+        skipped.extend(file_skipped);
+    }
+
+    Ok((sites, skipped))
+}
+
+fn collect_sources(dir: &Path, paths: &mut Vec<std::path::PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_sources(&path, paths)?;
+        } else if path.extension().is_some_and(|extension| extension == "cpp" || extension == "h") {
+            paths.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+pub fn scan_source(file: &str, source: &str) -> (Vec<CallSite>, Vec<SkippedCall>) {
+    let mut sites = Vec::new();
+    let mut skipped = Vec::new();
+
+    for (start, _) in source.match_indices(CALL) {
+        let before = source[..start].trim_end();
+        // Skip declarations and the definition, and names like `ThrowJSONRPCError(`
+        if before.ends_with("UniValue") || source[..start].ends_with(|c: char| c.is_alphanumeric() || c == '_') {
+            continue;
+        }
+
+        let line = source[..start].matches('\n').count() + 1;
+        let mut parser = Parser { source, pos: start + CALL.len() };
+        match parser.parse_arguments() {
+            Ok((name, message)) => sites.push(CallSite { file: file.to_string(), line, name, message }),
+            Err(reason) => skipped.push(SkippedCall { file: file.to_string(), line, reason }),
+        }
+    }
+
+    (sites, skipped)
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.source[self.pos..]
+    }
+
+    // Skips whitespace, line breaks and comments
+    fn skip_whitespace(&mut self) {
+        loop {
+            let rest = self.rest();
+            let trimmed = rest.trim_start();
+            self.pos += rest.len() - trimmed.len();
+
+            if trimmed.starts_with("//") {
+                self.pos += trimmed.find('\n').unwrap_or(trimmed.len());
+            } else if trimmed.starts_with("/*") {
+                self.pos += trimmed.find("*/").map_or(trimmed.len(), |end| end + 2);
+            } else {
+                return;
+            }
+        }
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        if self.rest().starts_with(token) {
+            self.pos += token.len();
+            return true;
+        }
+
+        false
+    }
+
+    fn parse_arguments(&mut self) -> Result<(String, Vec<Part>), &'static str> {
+        self.skip_whitespace();
+        let rest = self.rest();
+        let len = rest.find(|c: char| !(c.is_alphanumeric() || c == '_')).unwrap_or(rest.len());
+        let name = &rest[..len];
+        self.pos += len;
+        if !name.starts_with("RPC_") || !self.eat(",") {
+            return Err("code is not a constant");
+        }
+
+        let message = self.parse_message()?;
+        if !message.iter().any(|part| matches!(part, Part::Literal(_))) {
+            return Err("message is not a literal");
+        }
+
+        Ok((name.to_string(), message))
+    }
+
+    // Terms joined with `+` until the closing parenthesis of the call
+    fn parse_message(&mut self) -> Result<Vec<Part>, &'static str> {
+        let mut parts = Vec::new();
+        loop {
+            self.parse_term(&mut parts)?;
+            if self.eat(")") {
+                return Ok(merge_literals(parts));
+            }
+            if !self.eat("+") {
+                return Err("message is not terminated");
+            }
+        }
+    }
+
+    fn parse_term(&mut self, parts: &mut Vec<Part>) -> Result<(), &'static str> {
+        self.skip_whitespace();
+        let start = self.pos;
+
+        if let Some(literal) = self.parse_string_literals()? {
+            parts.push(Part::Literal(literal));
+        } else if self.eat("strprintf(") || self.eat("tfm::format(") {
+            match self.parse_string_literals()? {
+                Some(format) => {
+                    parts.extend(parse_format(&format));
+                    // The arguments of the format
+                    while self.eat(",") {
+                        self.skip_expression();
+                    }
+                    if !self.eat(")") {
+                        return Err("message is not terminated");
+                    }
+                }
+                None => {
+                    self.pos = start;
+                    self.skip_expression();
+                    parts.push(Part::Dynamic);
+                }
+            }
+        } else if self.eat("std::string(") {
+            match self.parse_string_literals()? {
+                Some(literal) if self.eat(")") => parts.push(Part::Literal(literal)),
+                _ => {
+                    self.pos = start;
+                    self.skip_expression();
+                    parts.push(Part::Dynamic);
+                }
+            }
+        } else {
+            self.skip_expression();
+            if self.pos == start {
+                return Err("message is not terminated");
+            }
+            parts.push(Part::Dynamic);
+        }
+
+        // Like `strprintf(..).c_str()`, the content stays the same
+        self.skip_whitespace();
+        if self.rest().starts_with('.') {
+            self.skip_expression();
+        }
+
+        Ok(())
+    }
+
+    // Skips to the next `+`, `,` or `)` that is not nested in parentheses or a string
+    fn skip_expression(&mut self) {
+        let mut depth = 0usize;
+        loop {
+            self.skip_whitespace();
+            let rest = self.rest();
+            let c = match rest.chars().next() {
+                Some(c) => c,
+                None => return,
+            };
+
+            match c {
+                '"' | '\'' => {
+                    if self.parse_quoted(c).is_err() {
+                        self.pos = self.source.len();
+                    }
+                    continue;
+                }
+                '(' | '[' | '{' => depth += 1,
+                ')' | ']' | '}' if depth == 0 => return,
+                ')' | ']' | '}' => depth -= 1,
+                '+' | ',' if depth == 0 => return,
+                _ => {}
+            }
+            self.pos += c.len_utf8();
+        }
+    }
+
+    // Adjacent string literals are concatenated
+    fn parse_string_literals(&mut self) -> Result<Option<String>, &'static str> {
+        let mut literal = String::new();
+        let mut num_literals = 0;
+        loop {
+            self.skip_whitespace();
+            let rest = self.rest();
+            let prefix_len = ["u8R", "u8", "LR", "uR", "UR", "L", "u", "U", "R", ""]
+                .iter()
+                .find(|prefix| rest.starts_with(*prefix) && rest[prefix.len()..].starts_with('"'))
+                .map(|prefix| prefix.len());
+            let prefix_len = match prefix_len {
+                Some(prefix_len) => prefix_len,
+                None => break,
+            };
+
+            self.pos += prefix_len;
+            if rest[..prefix_len].ends_with('R') {
+                literal.push_str(self.parse_raw_string()?);
+            } else {
+                literal.push_str(&unescape_cpp_string(self.parse_quoted('"')?));
+            }
+            num_literals += 1;
+        }
+
+        Ok((num_literals > 0).then_some(literal))
+    }
+
+    // Returns the content between the quotes, escaped quotes do not count
+    fn parse_quoted(&mut self, quote: char) -> Result<&'a str, &'static str> {
+        let rest = self.rest();
+        let mut chars = rest.char_indices().skip(1);
+        while let Some((i, c)) = chars.next() {
+            if c == '\\' {
+                chars.next();
+            } else if c == quote {
+                self.pos += i + 1;
+                return Ok(&rest[1..i]);
+            }
+        }
+
+        Err("message is not terminated")
+    }
+
+    // R"delimiter(...)delimiter"
+    fn parse_raw_string(&mut self) -> Result<&'a str, &'static str> {
+        let rest = self.rest();
+        let open = rest.find('(').ok_or("message is not terminated")?;
+        let close = format!("){}\"", &rest[1..open]);
+        let end = rest[open..].find(&close).ok_or("message is not terminated")? + open;
+        self.pos += end + close.len();
+
+        Ok(&rest[open + 1..end])
+    }
+}
+
+// https://github.com/c42f/tinyformat#format-specification
+pub fn parse_format(format: &str) -> Vec<Part> {
+    let mut parts = Vec::new();
+    let mut literal = String::new();
+    let mut chars = format.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            literal.push(c);
+            continue;
+        }
+        if chars.peek() == Some(&'%') {
+            chars.next();
+            literal.push('%');
+            continue;
+        }
+
+        // Flags, width, precision, positional arguments and length modifiers
+        while chars.peek().is_some_and(|c| "0123456789.-+ #$*'lhjztL".contains(*c)) {
+            chars.next();
+        }
+        let part = match chars.next() {
+            Some('d' | 'i') => Part::Integer,
+            Some('u') => Part::Unsigned,
+            Some(_) => Part::Text,
+            None => {
+                literal.push('%');
+                break;
+            }
+        };
+
+        if !literal.is_empty() {
+            parts.push(Part::Literal(std::mem::take(&mut literal)));
+        }
+        parts.push(part);
+    }
+
+    if !literal.is_empty() {
+        parts.push(Part::Literal(literal));
+    }
+
+    parts
+}
+
+fn merge_literals(parts: Vec<Part>) -> Vec<Part> {
+    let mut merged: Vec<Part> = Vec::with_capacity(parts.len());
+    for part in parts {
+        match (merged.last_mut(), part) {
+            (Some(Part::Literal(previous)), Part::Literal(literal)) => previous.push_str(&literal),
+            // `.*.*` matches the same as `.*`
+            (Some(Part::Dynamic), Part::Dynamic) => {}
+            (_, part) => merged.push(part),
+        }
+    }
+
+    merged
+}
+
+// https://en.cppreference.com/w/cpp/language/escape
+fn unescape_cpp_string(s: &str) -> String {
+    let mut output = String::with_capacity(s.len());
+    let mut chars = s.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            output.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('n') => output.push('\n'),
+            Some('t') => output.push('\t'),
+            Some('r') => output.push('\r'),
+            Some('0') => output.push('\0'),
+            Some(c) => output.push(c),
+            None => output.push('\\'),
+        }
+    }
+
+    output
+}
+
+// Writes a pattern as Rust string literal, raw if it contains backslashes or quotes
+pub fn to_rust_literal(pattern: &str) -> String {
+    if !pattern.contains(['\\', '"']) {
+        return format!("\"{}\"", pattern);
+    }
+
+    let mut hashes = String::new();
+    while pattern.contains(&format!("\"{}", hashes)) {
+        hashes.push('#');
+    }
+
+    format!("r{}\"{}\"{}", hashes, pattern, hashes)
+}
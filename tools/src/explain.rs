@@ -0,0 +1,147 @@
+// Explains an error of a node as pasted by an operator: the error object, the
+// whole JSON-RPC response or the stderr of bitcoin-cli
+use bitcoin_rpc_errors::{CoreVersion, Error, ErrorCategory, Remediation, RetryHint};
+use regex::Regex;
+use serde_json::{json, Value};
+
+use crate::classify;
+
+// Extracts code and message of the pasted error
+pub fn parse_input(input: &str) -> Result<(i32, String), String> {
+    let input = input.trim();
+
+    if let Ok(value) = serde_json::from_str::<Value>(input) {
+        // A response carries the error object next to `result` and `id`
+        let error = match value.get("error") {
+            Some(Value::Null) => return Err(String::from("The response is not an error")),
+            Some(error) => error,
+            None => &value,
+        };
+        let code = error.get("code").and_then(Value::as_i64).ok_or("The error has no `code`")?;
+        let message = error.get("message").and_then(Value::as_str).ok_or("The error has no `message`")?;
+        let code = i32::try_from(code).map_err(|_| format!("The code {} is out of range", code))?;
+
+        return Ok((code, message.to_string()));
+    }
+
+    // https://github.com/bitcoin/bitcoin/blob/v25.0/src/bitcoin-cli.cpp#L918
+    let regex = Regex::new(r"(?s)error code: (-?\d+)\s*\nerror message:\n(.*)").unwrap();
+    if let Some(captures) = regex.captures(input) {
+        let code = captures[1].parse().map_err(|_| format!("The code {} is out of range", &captures[1]))?;
+        return Ok((code, captures[2].trim_end().to_string()));
+    }
+
+    Err(String::from("Expected a JSON-RPC error, a JSON-RPC response or the output of bitcoin-cli"))
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Explanation {
+    pub code: i32,
+    pub message: String,
+    // The name of the code in protocol.h
+    pub name: &'static str,
+    // The enum and the variant the message was parsed into
+    pub enum_name: Option<&'static str>,
+    pub variant: Option<&'static str>,
+    pub fields: Vec<String>,
+    pub category: ErrorCategory,
+    pub remediation: Option<Remediation>,
+    pub retry: RetryHint,
+    // No pattern matched the message
+    pub fallback: bool,
+}
+
+pub fn explain(code: i32, message: &str, version: Option<CoreVersion>) -> Explanation {
+    let error = classify(code, message, version);
+    let (variant, fields) = error.message_variant();
+    let enum_name = Error::VARIANTS
+        .iter()
+        .find(|info| info.code == code)
+        .and_then(|info| info.enum_name);
+
+    Explanation {
+        code,
+        message: message.to_string(),
+        name: error.name(),
+        enum_name: variant.and(enum_name),
+        variant,
+        fields: fields.iter().map(|field| format!("{:?}", field)).collect(),
+        category: error.category(),
+        remediation: error.remediation(),
+        retry: error.retry_hint(),
+        fallback: error.is_fallback(),
+    }
+}
+
+impl Explanation {
+    pub fn to_text(&self) -> String {
+        let variant = match (self.enum_name, self.variant) {
+            (Some(enum_name), Some(variant)) => format!("{}::{}", enum_name, variant),
+            _ => String::from("-"),
+        };
+        let mut text = format!("{} ({})\n", self.name, self.code);
+        text.push_str(&format!("variant:  {}\n", variant));
+        if !self.fields.is_empty() {
+            text.push_str(&format!("fields:   {}\n", self.fields.join(", ")));
+        }
+        text.push_str(&format!("category: {:?}\n", self.category));
+        match self.retry.backoff {
+            Some(backoff) => text.push_str(&format!("retry:    {:?}, after {:?}\n", self.retry.class, backoff)),
+            None => text.push_str(&format!("retry:    {:?}\n", self.retry.class)),
+        }
+        text.push_str(&format!("message:  {}\n", self.message));
+        if let Some(remediation) = &self.remediation {
+            text.push('\n');
+            text.push_str(&remediation_text(remediation));
+        }
+        if self.fallback {
+            text.push_str("\nNo pattern matched this message, consider adding it to tests/fixtures\n");
+        }
+
+        text
+    }
+
+    pub fn to_json(&self) -> Value {
+        // Captured strings without the quotes of their Debug output
+        let fields: Vec<Value> = self
+            .fields
+            .iter()
+            .map(|field| serde_json::from_str::<String>(field).map_or_else(|_| Value::from(field.as_str()), Value::from))
+            .collect();
+
+        json!({
+            "code": self.code,
+            "name": self.name,
+            "enum": self.enum_name,
+            "variant": self.variant,
+            "fields": fields,
+            "category": format!("{:?}", self.category),
+            "remediation": self.remediation.map(|remediation| json!({
+                "rpc": remediation.rpc,
+                "flag": remediation.flag,
+                "docs": remediation.docs,
+            })),
+            "retry": {
+                "class": format!("{:?}", self.retry.class),
+                "backoff_ms": self.retry.backoff.map(|backoff| backoff.as_millis() as u64),
+            },
+            "fallback": self.fallback,
+            "message": self.message,
+        })
+    }
+}
+
+fn remediation_text(remediation: &Remediation) -> String {
+    let mut text = String::new();
+    if let Some(rpc) = remediation.rpc {
+        text.push_str(&format!("Try calling `{}`\n", rpc));
+    }
+    if let Some(flag) = remediation.flag {
+        text.push_str(&format!("Try starting the node with `{}`\n", flag));
+    }
+    if let Some(docs) = remediation.docs {
+        text.push_str(&format!("See {}\n", docs));
+    }
+
+    text
+}
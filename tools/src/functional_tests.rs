@@ -0,0 +1,240 @@
+// Extracts the errors Bitcoin Core's functional tests expect from
+// `assert_raises_rpc_error(code, message, ...)` calls
+// https://github.com/bitcoin/bitcoin/blob/v25.0/test/functional/test_framework/util.py
+use std::{fs, io, path::Path};
+
+const CALL: &str = "assert_raises_rpc_error(";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExpectedError {
+    // Relative to the Core checkout, e.g. test/functional/wallet_basic.py
+    pub file: String,
+    pub line: usize,
+    pub code: i32,
+    // The functional tests only check that the message contains this string
+    pub message: String,
+}
+
+// A call whose code or message is not a literal, e.g. an f-string
+#[derive(Clone, Debug, PartialEq)]
+pub struct SkippedCall {
+    pub file: String,
+    pub line: usize,
+    pub reason: &'static str,
+}
+
+// Reads all functional tests of the Core checkout at `core_dir`
+pub fn extract_from_checkout(core_dir: &Path) -> io::Result<(Vec<ExpectedError>, Vec<SkippedCall>)> {
+    let mut paths: Vec<_> = fs::read_dir(core_dir.join("test/functional"))?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<io::Result<_>>()?;
+    paths.retain(|path| path.extension().is_some_and(|extension| extension == "py"));
+    paths.sort();
+
+    let mut errors = Vec::new();
+    let mut skipped = Vec::new();
+    for path in paths {
+        let file = path.strip_prefix(core_dir).unwrap_or(&path).to_string_lossy().to_string();
+        let (file_errors, file_skipped) = extract_from_source(&file, &fs::read_to_string(&path)?);
+        errors.extend(file_errors);
+        skipped.extend(file_skipped);
+    }
+
+    Ok((errors, skipped))
+}
+
+pub fn extract_from_source(file: &str, source: &str) -> (Vec<ExpectedError>, Vec<SkippedCall>) {
+    let mut errors = Vec::new();
+    let mut skipped = Vec::new();
+
+    for (start, _) in source.match_indices(CALL) {
+        // Skip the definition of the helper itself
+        if source[..start].ends_with("def ") {
+            continue;
+        }
+
+        let line = source[..start].matches('\n').count() + 1;
+        let mut parser = Parser { source, pos: start + CALL.len() };
+        match parser.parse_arguments() {
+            Ok((code, message)) => errors.push(ExpectedError { file: file.to_string(), line, code, message }),
+            Err(reason) => skipped.push(SkippedCall { file: file.to_string(), line, reason }),
+        }
+    }
+
+    (errors, skipped)
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.source[self.pos..]
+    }
+
+    // Skips whitespace, line breaks and comments
+    fn skip_whitespace(&mut self) {
+        loop {
+            let rest = self.rest();
+            let trimmed = rest.trim_start();
+            self.pos += rest.len() - trimmed.len();
+
+            if trimmed.starts_with('#') {
+                self.pos += trimmed.find('\n').unwrap_or(trimmed.len());
+            } else if trimmed.starts_with("\\\n") {
+                self.pos += 2;
+            } else {
+                return;
+            }
+        }
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        if self.rest().starts_with(token) {
+            self.pos += token.len();
+            return true;
+        }
+
+        false
+    }
+
+    fn parse_arguments(&mut self) -> Result<(i32, String), &'static str> {
+        let code = self.parse_code()?;
+        if !self.eat(",") {
+            return Err("code is not a literal");
+        }
+
+        let message = self.parse_message()?;
+        self.skip_whitespace();
+        if !(self.rest().starts_with(',') || self.rest().starts_with(')')) {
+            return Err("message is not a literal");
+        }
+
+        Ok((code, message))
+    }
+
+    fn parse_code(&mut self) -> Result<i32, &'static str> {
+        self.skip_whitespace();
+        let rest = self.rest();
+        let len = rest
+            .char_indices()
+            .find(|&(i, c)| !(c.is_ascii_digit() || (i == 0 && c == '-')))
+            .map_or(rest.len(), |(i, _)| i);
+        let code = rest[..len].parse().map_err(|_| "code is not a literal")?;
+        self.pos += len;
+
+        Ok(code)
+    }
+
+    // Adjacent string literals are concatenated, they may be wrapped in parentheses
+    fn parse_message(&mut self) -> Result<String, &'static str> {
+        let parenthesized = self.eat("(");
+
+        let mut message = String::new();
+        let mut num_literals = 0;
+        loop {
+            self.skip_whitespace();
+            match self.parse_string_literal()? {
+                Some(literal) => message.push_str(&literal),
+                None => break,
+            }
+            num_literals += 1;
+        }
+
+        if num_literals == 0 {
+            return Err("message is not a literal");
+        }
+        if parenthesized && !self.eat(")") {
+            return Err("message is not a literal");
+        }
+
+        Ok(message)
+    }
+
+    fn parse_string_literal(&mut self) -> Result<Option<String>, &'static str> {
+        let rest = self.rest();
+        let prefix_len = rest.find(['\'', '"']).unwrap_or(0);
+        let prefix = &rest[..prefix_len];
+        if !prefix.chars().all(|c| "rRbBuUfF".contains(c)) || prefix.len() > 2 {
+            return Ok(None);
+        }
+        if !rest[prefix_len..].starts_with(['\'', '"']) {
+            return Ok(None);
+        }
+        if prefix.contains(['f', 'F']) {
+            return Err("message is an f-string");
+        }
+
+        let body = &rest[prefix_len..];
+        let quote = if body.starts_with("\"\"\"") || body.starts_with("'''") { &body[..3] } else { &body[..1] };
+        let content_start = prefix_len + quote.len();
+
+        // Find the closing quote, escaped quotes do not count
+        let mut end = None;
+        let mut chars = rest[content_start..].char_indices();
+        while let Some((i, c)) = chars.next() {
+            if c == '\\' {
+                chars.next();
+            } else if rest[content_start + i..].starts_with(quote) {
+                end = Some(content_start + i);
+                break;
+            }
+        }
+        let end = end.ok_or("message is not terminated")?;
+
+        let content = &rest[content_start..end];
+        let literal = if prefix.contains(['r', 'R']) { content.to_string() } else { unescape_python_string(content) };
+        self.pos += end + quote.len();
+
+        Ok(Some(literal))
+    }
+}
+
+// https://docs.python.org/3/reference/lexical_analysis.html#escape-sequences
+fn unescape_python_string(s: &str) -> String {
+    let mut output = String::with_capacity(s.len());
+    let mut chars = s.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            output.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('\n') => {}
+            Some('n') => output.push('\n'),
+            Some('t') => output.push('\t'),
+            Some('r') => output.push('\r'),
+            Some('0') => output.push('\0'),
+            Some(escape @ ('x' | 'u' | 'U')) => {
+                let len = match escape {
+                    'x' => 2,
+                    'u' => 4,
+                    _ => 8,
+                };
+                let hex: String = chars.by_ref().take(len).collect();
+                match u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32) {
+                    Some(c) => output.push(c),
+                    None => {
+                        output.push('\\');
+                        output.push(escape);
+                        output.push_str(&hex);
+                    }
+                }
+            }
+            Some(c @ ('\\' | '\'' | '"')) => output.push(c),
+            // Unknown escapes are kept as they are
+            Some(c) => {
+                output.push('\\');
+                output.push(c);
+            }
+            None => output.push('\\'),
+        }
+    }
+
+    output
+}
@@ -0,0 +1,110 @@
+// Tools that compare bitcoin-rpc-errors with a local Bitcoin Core checkout and
+// explain parsed errors
+pub mod call_sites;
+pub mod explain;
+pub mod functional_tests;
+pub mod reference;
+
+#[cfg(test)]
+mod test;
+
+use bitcoin_rpc_errors::{CoreVersion, Error};
+use serde_json::{json, Value};
+
+// Links a line of the Core source, `tag` is a release like v25.0 or a branch
+pub fn github_link(tag: &str, file: &str, line: usize) -> String {
+    format!("https://github.com/bitcoin/bitcoin/blob/{}/{}#L{}", tag, file, line)
+}
+
+// Parses a message with the patterns of `version`, or all patterns without one
+pub fn classify(code: i32, message: &str, version: Option<CoreVersion>) -> Error {
+    match version {
+        Some(version) => Error::from_code_for_version(code, message, version),
+        None => Error::from_code(code, message),
+    }
+}
+
+// The functional tests only check that the message contains the expected string, which is
+// often a fragment. Returns the variant of `code` with a message containing the fragment,
+// the parts of a message captured by a pattern do not count.
+pub fn partial_match(code: i32, fragment: &str) -> Option<&'static str> {
+    if fragment.is_empty() {
+        return None;
+    }
+
+    let info = Error::VARIANTS.iter().find(|info| info.code == code)?;
+    let variant = info.variants.iter().find(|variant| {
+        variant.patterns.iter().any(|pattern| {
+            reference::example_message(pattern).split('…').any(|part| part.contains(fragment))
+        })
+    })?;
+
+    Some(variant.name)
+}
+
+// A fixture for tests/fixtures, the expected variant is left empty to be filled in
+pub fn fixture(code: i32, message: &str, source: &str) -> Value {
+    json!({
+        "code": code,
+        "message": message,
+        "expected": "",
+        "source": source,
+    })
+}
+
+// Formats JSON like the files in tests/fixtures
+pub fn to_fixture_json(value: &Value) -> String {
+    let mut output = Vec::new();
+    let formatter = serde_json::ser::PrettyFormatter::with_indent(b"    ");
+    let mut serializer = serde_json::Serializer::with_formatter(&mut output, formatter);
+    serde::Serialize::serialize(value, &mut serializer).unwrap();
+
+    String::from_utf8(output).unwrap() + "\n"
+}
+
+// The arguments of a tool, positional arguments, `--name value` options and
+// `--name` flags without a value
+pub struct Args {
+    pub positional: Vec<String>,
+    options: Vec<(String, String)>,
+    flags: Vec<String>,
+}
+
+impl Args {
+    pub fn from_env(flags: &[&str]) -> Result<Self, String> {
+        Self::parse(std::env::args().skip(1), flags)
+    }
+
+    pub fn parse(args: impl IntoIterator<Item = String>, flags: &[&str]) -> Result<Self, String> {
+        let mut positional = Vec::new();
+        let mut options = Vec::new();
+        let mut set_flags = Vec::new();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.strip_prefix("--") {
+                Some(name) if flags.contains(&name) => set_flags.push(name.to_string()),
+                Some(name) => {
+                    let value = args.next().ok_or_else(|| format!("--{} needs a value", name))?;
+                    options.push((name.to_string(), value));
+                }
+                None => positional.push(arg),
+            }
+        }
+
+        Ok(Args { positional, options, flags: set_flags })
+    }
+
+    pub fn flag(&self, name: &str) -> bool {
+        self.flags.iter().any(|flag| flag == name)
+    }
+
+    pub fn option(&self, name: &str) -> Option<&str> {
+        self.options.iter().find(|(option, _)| option == name).map(|(_, value)| value.as_str())
+    }
+}
+
+// Prints the message and exits the tool with an error
+pub fn exit(message: &str) -> ! {
+    eprintln!("{}", message);
+    std::process::exit(1)
+}
@@ -0,0 +1,389 @@
+// Generates the error reference in docs/ and the "State" checklist of the README
+// from `Error::VARIANTS`. The Core links and descriptions are `//` comments the
+// derives can not see, they are read from the source of the crate instead.
+use bitcoin_rpc_errors::{CodeInfo, Remediation, VariantInfo};
+use regex::Regex;
+use regex_syntax::hir::{Class, Hir, HirKind};
+use std::{collections::HashMap, fs, io, path::Path};
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SourceComments {
+    // (enum, variant) -> links to the Core source
+    links: HashMap<(String, String), Vec<String>>,
+    // Code name -> the comment of the code in `Error`
+    descriptions: HashMap<String, String>,
+    // The sections of `Error` like "Wallet errors" with their code names in order
+    sections: Vec<(String, Vec<String>)>,
+}
+
+impl SourceComments {
+    pub fn links(&self, enum_name: &str, variant: &str) -> &[String] {
+        self.links
+            .get(&(enum_name.to_string(), variant.to_string()))
+            .map_or(&[], |links| links.as_slice())
+    }
+
+    pub fn description(&self, name: &str) -> &str {
+        self.descriptions.get(name).map_or("", |description| description.as_str())
+    }
+}
+
+// Reads the comments of src/*.rs of the crate at `crate_dir`
+pub fn read_source_comments(crate_dir: &Path) -> io::Result<SourceComments> {
+    let mut paths: Vec<_> = fs::read_dir(crate_dir.join("src"))?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<io::Result<_>>()?;
+    paths.retain(|path| path.extension().is_some_and(|extension| extension == "rs"));
+    paths.sort();
+
+    let mut comments = SourceComments::default();
+    for path in paths {
+        parse_source_comments(&fs::read_to_string(path)?, &mut comments);
+    }
+
+    Ok(comments)
+}
+
+pub fn parse_source_comments(source: &str, comments: &mut SourceComments) {
+    let enum_regex = Regex::new(r"^pub enum (\w+)").unwrap();
+    let variant_regex = Regex::new(r"^\s*(?:#\[code\(-?\d+(?:, category = \w+)?\)\])?\s*([A-Z]\w*)\s*(?:[,({]|$)(?:.*?//\s*(.*))?").unwrap();
+    let section_regex = Regex::new(r"^\s*//\s*(.*)$").unwrap();
+
+    let mut current_enum: Option<&str> = None;
+    let mut links = Vec::new();
+    // Links above the enum are the source of variants without their own links
+    let mut enum_links = Vec::new();
+    let mut fallback = false;
+    for line in source.lines() {
+        if let Some(captures) = enum_regex.captures(line) {
+            current_enum = captures.get(1).map(|name| name.as_str());
+            enum_links = std::mem::take(&mut links);
+            continue;
+        }
+        let trimmed = line.trim();
+        let enum_name = match current_enum {
+            Some(enum_name) => enum_name,
+            None => {
+                match trimmed.strip_prefix("// https://github.com/bitcoin/bitcoin/") {
+                    Some(link) => links.push(format!("https://github.com/bitcoin/bitcoin/{}", link.trim_end())),
+                    None if !trimmed.starts_with("#[") => links.clear(),
+                    None => {}
+                }
+                continue;
+            }
+        };
+
+        if line.starts_with('}') {
+            current_enum = None;
+        } else if trimmed.is_empty() {
+            links.clear();
+        } else if trimmed == "#[fallback]" {
+            fallback = true;
+        } else if let Some(link) = trimmed.strip_prefix("// https://github.com/bitcoin/bitcoin/") {
+            links.push(format!("https://github.com/bitcoin/bitcoin/{}", link.trim_end()));
+        } else if let Some(captures) = variant_regex.captures(line) {
+            let variant = captures[1].to_string();
+            if enum_name == "Error" {
+                let description = captures.get(2).map_or("", |description| description.as_str().trim());
+                comments.descriptions.insert(variant.clone(), description.to_string());
+                match comments.sections.last_mut() {
+                    Some((_, names)) => names.push(variant.clone()),
+                    None => comments.sections.push((String::new(), vec![variant.clone()])),
+                }
+            }
+            let links = match links.is_empty() && !fallback {
+                true => enum_links.clone(),
+                false => std::mem::take(&mut links),
+            };
+            fallback = false;
+            comments.links.insert((enum_name.to_string(), variant), links);
+        } else if let Some(captures) = section_regex.captures(line) {
+            // The codes of `Error` are grouped like in protocol.h
+            if enum_name == "Error" {
+                comments.sections.push((captures[1].to_string(), Vec::new()));
+            }
+        }
+    }
+}
+
+// A message the pattern matches, captures that match anything become "…"
+pub fn example_message(pattern: &str) -> String {
+    let mut example = String::new();
+    if let Ok(hir) = regex_syntax::parse(pattern) {
+        write_example(&hir, &mut example);
+    }
+
+    example
+}
+
+fn write_example(hir: &Hir, example: &mut String) {
+    match hir.kind() {
+        HirKind::Empty | HirKind::Look(_) => {}
+        HirKind::Literal(literal) => example.push_str(&String::from_utf8_lossy(&literal.0)),
+        HirKind::Class(class) => example.push(example_char(class)),
+        HirKind::Repetition(repetition) => {
+            if matches!(repetition.sub.kind(), HirKind::Class(class) if matches_anything(class)) {
+                example.push('…');
+                return;
+            }
+            // Optional parts are left out, `*` repeats once like `+`
+            for _ in 0..repetition.min.max(u32::from(repetition.max.is_none())) {
+                write_example(&repetition.sub, example);
+            }
+        }
+        HirKind::Capture(capture) => write_example(&capture.sub, example),
+        HirKind::Concat(hirs) => hirs.iter().for_each(|hir| write_example(hir, example)),
+        HirKind::Alternation(hirs) => write_example(&hirs[0], example),
+    }
+}
+
+// Like `.` or `[^"]`
+fn matches_anything(class: &Class) -> bool {
+    let contains = |c: char| match class {
+        Class::Unicode(class) => class.ranges().iter().any(|range| range.start() <= c && c <= range.end()),
+        Class::Bytes(class) => class.ranges().iter().any(|range| range.start() <= c as u8 && c as u8 <= range.end()),
+    };
+
+    contains('a') && contains('0') && contains(' ')
+}
+
+// Digits and letters read better than the first character of a class
+fn example_char(class: &Class) -> char {
+    // An unescaped `.` in the patterns is usually meant literally
+    if matches_anything(class) {
+        return '.';
+    }
+
+    let chars: Vec<(char, char)> = match class {
+        Class::Unicode(class) => class.ranges().iter().map(|range| (range.start(), range.end())).collect(),
+        Class::Bytes(class) => class.ranges().iter().map(|range| (range.start() as char, range.end() as char)).collect(),
+    };
+
+    ['0', 'a', 'A']
+        .into_iter()
+        .find(|c| chars.iter().any(|(start, end)| start <= c && c <= end))
+        .or_else(|| chars.first().map(|(start, _)| *start))
+        .unwrap_or('?')
+}
+
+// Checked codes parse their message, struck through codes ignore it
+fn status(info: &CodeInfo) -> (bool, bool) {
+    let parsed = info.variants.iter().any(|variant| !variant.fallback && !variant.patterns.is_empty());
+    match info.enum_name {
+        Some(_) => (parsed, false),
+        None => (true, true),
+    }
+}
+
+// The "State" section of the README
+pub fn checklist(codes: &[CodeInfo], comments: &SourceComments) -> String {
+    let mut output = String::from(
+        "Generated from `Error::VARIANTS` with `generate-reference`. Checked codes parse their message, \
+         ~~struck through~~ codes do not need parsing and ignore it. \
+         Every variant and pattern is listed in the [error reference](docs/errors.md).\n",
+    );
+
+    for (section, names) in &comments.sections {
+        output.push_str(&format!("\n### {}\n", section));
+        for name in names {
+            // The fallback has no code
+            let (checked, ignored) = codes
+                .iter()
+                .find(|info| info.name == name)
+                .map_or((true, false), status);
+            output.push_str(&format!(
+                "- [{}] {} // {}\n",
+                if checked { "x" } else { " " },
+                if ignored { format!("~~{}~~", name) } else { name.to_string() },
+                comments.description(name)
+            ));
+        }
+    }
+
+    output
+}
+
+// Replaces the "State" section of the README with the checklist
+pub fn update_readme(readme: &str, checklist: &str) -> Option<String> {
+    let start = readme.find("## State\n")? + "## State\n".len();
+    let end = start + readme[start..].find("\n## ")?;
+
+    Some(format!("{}{}{}", &readme[..start], checklist, &readme[end..]))
+}
+
+// A row of the reference, one per variant
+struct Row<'a> {
+    variant: &'a VariantInfo,
+    // The remediation of the variant, else the one of its code
+    remediation: Option<Remediation>,
+    examples: Vec<String>,
+    links: &'a [String],
+}
+
+fn rows<'a>(info: &'a CodeInfo, comments: &'a SourceComments) -> Vec<Row<'a>> {
+    info.variants
+        .iter()
+        .map(|variant| Row {
+            variant,
+            remediation: variant.remediation.or(info.remediation),
+            examples: variant.patterns.iter().map(|pattern| example_message(pattern)).collect(),
+            links: comments.links(info.enum_name.unwrap_or_default(), variant.name),
+        })
+        .collect()
+}
+
+// The RPC to call, the option to set and the docs of a variant, formatted for
+// Markdown or HTML
+fn remediation_parts(remediation: &Option<Remediation>, code: impl Fn(&str) -> String, link: impl Fn(&str) -> String) -> String {
+    let remediation = match remediation {
+        Some(remediation) => remediation,
+        None => return String::new(),
+    };
+
+    let mut parts = Vec::new();
+    if let Some(rpc) = remediation.rpc {
+        parts.push(format!("call {}", code(rpc)));
+    }
+    if let Some(flag) = remediation.flag {
+        parts.push(format!("start with {}", code(flag)));
+    }
+    if let Some(docs) = remediation.docs {
+        parts.push(link(docs));
+    }
+
+    parts.join("<br>")
+}
+
+// The file and line of a link, e.g. src/rpc/blockchain.cpp#L459
+fn link_text(link: &str) -> &str {
+    link.splitn(8, '/').nth(7).unwrap_or(link)
+}
+
+fn codes_in_sections<'a>(codes: &'a [CodeInfo], comments: &'a SourceComments) -> Vec<(&'a str, Vec<&'a CodeInfo>)> {
+    comments
+        .sections
+        .iter()
+        .map(|(section, names)| {
+            let infos: Vec<&CodeInfo> = names.iter().filter_map(|name| codes.iter().find(|info| info.name == name)).collect();
+            (section.as_str(), infos)
+        })
+        .filter(|(_, infos)| !infos.is_empty())
+        .collect()
+}
+
+pub fn markdown(codes: &[CodeInfo], comments: &SourceComments) -> String {
+    let escape = |s: &str| s.replace('|', r"\|").replace('\n', r"\n");
+    let mut output = String::from(
+        "# Error reference\n\n\
+         Generated from `Error::VARIANTS` with `generate-reference`, do not edit by hand.\n",
+    );
+
+    for (section, infos) in codes_in_sections(codes, comments) {
+        output.push_str(&format!("\n## {}\n", section));
+        for info in infos {
+            output.push_str(&format!("\n### {} {}\n\n{}\n", info.code, info.name, comments.description(info.name)));
+            let enum_name = match info.enum_name {
+                Some(enum_name) => enum_name,
+                None => {
+                    output.push_str("\nThe message is ignored.\n");
+                    continue;
+                }
+            };
+
+            output.push_str(&format!("\nParsed into `{}`.\n\n", enum_name));
+            output.push_str("| Variant | Pattern | Example message | Remediation | Source |\n");
+            output.push_str("|---|---|---|---|---|\n");
+            for row in rows(info, comments) {
+                let patterns = if row.variant.fallback {
+                    String::from("*fallback*")
+                } else {
+                    row.variant.patterns.iter().map(|pattern| format!("`{}`", escape(pattern))).collect::<Vec<_>>().join("<br>")
+                };
+                let examples = row.examples.iter().map(|example| escape(example)).collect::<Vec<_>>().join("<br>");
+                let links = row
+                    .links
+                    .iter()
+                    .map(|link| format!("[{}]({})", link_text(link), link))
+                    .collect::<Vec<_>>()
+                    .join("<br>");
+                let remediation = remediation_parts(&row.remediation, |code| format!("`{}`", code), |docs| format!("[docs]({})", docs));
+                output.push_str(&format!(
+                    "| `{}` | {} | {} | {} | {} |\n",
+                    row.variant.name, patterns, examples, remediation, links
+                ));
+            }
+        }
+    }
+
+    output
+}
+
+pub fn html(codes: &[CodeInfo], comments: &SourceComments) -> String {
+    let escape = |s: &str| {
+        s.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+            .replace('\n', r"\n")
+    };
+    let mut output = String::from(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Error reference</title>\n</head>\n<body>\n\
+         <h1>Error reference</h1>\n\
+         <p>Generated from <code>Error::VARIANTS</code> with <code>generate-reference</code>, do not edit by hand.</p>\n",
+    );
+
+    for (section, infos) in codes_in_sections(codes, comments) {
+        output.push_str(&format!("<h2>{}</h2>\n", escape(section)));
+        for info in infos {
+            output.push_str(&format!(
+                "<h3 id=\"{}\">{} {}</h3>\n<p>{}</p>\n",
+                info.name,
+                info.code,
+                info.name,
+                escape(comments.description(info.name))
+            ));
+            let enum_name = match info.enum_name {
+                Some(enum_name) => enum_name,
+                None => {
+                    output.push_str("<p>The message is ignored.</p>\n");
+                    continue;
+                }
+            };
+
+            output.push_str(&format!("<p>Parsed into <code>{}</code>.</p>\n", enum_name));
+            output.push_str("<table>\n<tr><th>Variant</th><th>Pattern</th><th>Example message</th><th>Remediation</th><th>Source</th></tr>\n");
+            for row in rows(info, comments) {
+                let patterns = if row.variant.fallback {
+                    String::from("<em>fallback</em>")
+                } else {
+                    row.variant
+                        .patterns
+                        .iter()
+                        .map(|pattern| format!("<code>{}</code>", escape(pattern)))
+                        .collect::<Vec<_>>()
+                        .join("<br>")
+                };
+                let examples = row.examples.iter().map(|example| escape(example)).collect::<Vec<_>>().join("<br>");
+                let links = row
+                    .links
+                    .iter()
+                    .map(|link| format!("<a href=\"{}\">{}</a>", escape(link), escape(link_text(link))))
+                    .collect::<Vec<_>>()
+                    .join("<br>");
+                let remediation = remediation_parts(
+                    &row.remediation,
+                    |code| format!("<code>{}</code>", escape(code)),
+                    |docs| format!("<a href=\"{}\">docs</a>", escape(docs)),
+                );
+                output.push_str(&format!(
+                    "<tr><td><code>{}</code></td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                    row.variant.name, patterns, examples, remediation, links
+                ));
+            }
+            output.push_str("</table>\n");
+        }
+    }
+
+    output.push_str("</body>\n</html>\n");
+    output
+}
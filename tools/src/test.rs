@@ -0,0 +1,276 @@
+use crate::call_sites::{parse_codes, parse_format, scan_source, to_rust_literal, Part};
+use crate::explain::{explain, parse_input};
+use crate::functional_tests::{extract_from_source, ExpectedError};
+use crate::reference::{self, example_message, parse_source_comments, SourceComments};
+use crate::{classify, fixture, partial_match, to_fixture_json, Args};
+use bitcoin_rpc_errors::{Error, ErrorCategory, RetryClass};
+use std::path::Path;
+
+const FUNCTIONAL_TEST: &str = r#"
+from test_framework.util import assert_raises_rpc_error
+
+class WalletTest(BitcoinTestFramework):
+    def run_test(self):
+        assert_raises_rpc_error(-18, "Requested wallet does not exist or is not loaded", node.getwalletinfo)
+        assert_raises_rpc_error(-8, "Invalid parameter, "  # split message
+                                    'missing vout key', node.createrawtransaction, [{}], {})
+        assert_raises_rpc_error(-35, 'Wallet \"w1\" is already loaded.', node.loadwallet, 'w1')
+        assert_raises_rpc_error(-4, f"Error: {name} failed", node.dumpwallet, name)
+        assert_raises_rpc_error(-5, "Invalid address" + suffix, node.getaddressinfo, "")
+        assert_raises_rpc_error(None, "Unknown error", node.stop)
+"#;
+
+#[test]
+fn extract_functional_tests() {
+    let (errors, skipped) = extract_from_source("test/functional/wallet_test.py", FUNCTIONAL_TEST);
+
+    assert_eq!(
+        errors[0],
+        ExpectedError {
+            file: String::from("test/functional/wallet_test.py"),
+            line: 6,
+            code: -18,
+            message: String::from("Requested wallet does not exist or is not loaded"),
+        }
+    );
+    // Adjacent literals are concatenated across lines and comments
+    assert_eq!(errors[1].message, "Invalid parameter, missing vout key");
+    assert_eq!(errors[2].message, r#"Wallet "w1" is already loaded."#);
+    assert_eq!(errors.len(), 3);
+
+    let reasons: Vec<&str> = skipped.iter().map(|call| call.reason).collect();
+    assert_eq!(reasons, ["message is an f-string", "message is not a literal", "code is not a literal"]);
+    assert_eq!(skipped[0].line, 10);
+}
+
+#[test]
+fn classify_and_fixtures() {
+    assert!(!classify(-18, "Requested wallet does not exist or is not loaded", None).is_fallback());
+    assert!(classify(-18, "Something new", "v25.0".parse().ok()).is_fallback());
+
+    // The functional tests often expect only a part of the message
+    assert!(classify(-8, "missing vout key", None).is_fallback());
+    assert_eq!(partial_match(-8, "missing vout key"), Some("MissingVout"));
+    assert_eq!(partial_match(-18, "Something new"), None);
+    assert_eq!(partial_match(-8, ""), None);
+
+    let fixtures = serde_json::Value::from(vec![fixture(-18, "Something new", "https://github.com/bitcoin/bitcoin/blob/v25.0/test/functional/wallet_test.py#L6")]);
+    assert_eq!(
+        to_fixture_json(&fixtures),
+        r#"[
+    {
+        "code": -18,
+        "expected": "",
+        "message": "Something new",
+        "source": "https://github.com/bitcoin/bitcoin/blob/v25.0/test/functional/wallet_test.py#L6"
+    }
+]
+"#
+    );
+}
+
+#[test]
+fn args() {
+    let args = Args::parse(["../bitcoin", "--tag", "v25.0", "--json"].map(String::from), &["json"]).unwrap();
+    assert_eq!(args.positional, ["../bitcoin"]);
+    assert_eq!(args.option("tag"), Some("v25.0"));
+    assert_eq!(args.option("fixtures"), None);
+    assert!(args.flag("json"));
+
+    assert!(Args::parse(["--tag"].map(String::from), &[]).is_err());
+}
+
+const CORE_SOURCE: &str = r#"
+UniValue JSONRPCError(int code, const std::string& message);
+
+static RPCHelpMan getblock()
+{
+    if (!pblockindex) {
+        throw JSONRPCError(RPC_INVALID_ADDRESS_OR_KEY, "Block not found");
+    }
+    if (height < 0 || height > active_chain.Height()) {
+        throw JSONRPCError(RPC_INVALID_PARAMETER, strprintf("Target block height %d after current tip %u", height, tip));
+    }
+    // Adjacent literals and concatenation
+    throw JSONRPCError(RPC_WALLET_NOT_FOUND, "Requested wallet does not exist "
+                                             "or is not loaded: " + request.params[0].get_str());
+    return JSONRPCError(RPC_TYPE_ERROR, std::string("Invalid \"type\": ") + type + ".");
+    throw JSONRPCError(RPC_MISC_ERROR, error.original);
+    throw JSONRPCError(code, "Unknown");
+}
+"#;
+
+#[test]
+fn scan_call_sites() {
+    let (sites, skipped) = scan_source("src/rpc/blockchain.cpp", CORE_SOURCE);
+
+    assert_eq!(sites[0].line, 7);
+    assert_eq!(sites[0].name, "RPC_INVALID_ADDRESS_OR_KEY");
+    assert_eq!(sites[0].message, [Part::Literal(String::from("Block not found"))]);
+    assert_eq!(sites[0].to_pattern(), "^Block not found$");
+
+    assert_eq!(sites[1].to_pattern(), r"^Target block height (-?\d+) after current tip (\d+)$");
+    assert_eq!(sites[1].sample_message(), "Target block height 0 after current tip 0");
+    assert_eq!(sites[1].captures(), 2);
+
+    assert_eq!(sites[2].to_pattern(), "^Requested wallet does not exist or is not loaded: .*$");
+    assert_eq!(sites[2].captures(), 0);
+    assert_eq!(sites[3].to_pattern(), r#"^Invalid "type": .*\.$"#);
+    assert_eq!(sites.len(), 4);
+
+    let reasons: Vec<&str> = skipped.iter().map(|call| call.reason).collect();
+    assert_eq!(reasons, ["message is not a literal", "code is not a constant"]);
+}
+
+#[test]
+fn call_site_helpers() {
+    assert_eq!(
+        parse_format("%s is 100%% %08.3f"),
+        [Part::Text, Part::Literal(String::from(" is 100% ")), Part::Text]
+    );
+
+    let codes = parse_codes("RPC_VERIFY_ERROR = -25, //!< General error\nRPC_TRANSACTION_ERROR = RPC_VERIFY_ERROR,");
+    assert_eq!(codes["RPC_VERIFY_ERROR"], -25);
+    assert_eq!(codes["RPC_TRANSACTION_ERROR"], -25);
+
+    assert_eq!(to_rust_literal("^Block not found$"), r#""^Block not found$""#);
+    assert_eq!(to_rust_literal(r"^Invalid \(pruned\)$"), r#"r"^Invalid \(pruned\)$""#);
+    assert_eq!(to_rust_literal(r#"^Invalid "type"$"#), r##"r#"^Invalid "type"$"#"##);
+}
+
+#[test]
+fn examples() {
+    assert_eq!(example_message("^Block header missing$"), "Block header missing");
+    assert_eq!(example_message("^Rescan aborted.$"), "Rescan aborted.");
+    assert_eq!(example_message(r"^Transaction (\d+) not in mempool \((.*)\)$"), "Transaction 0 not in mempool (…)");
+    assert_eq!(example_message("^(?:Wallet file verification failed. )?Failed to load (.*)"), "Failed to load …");
+
+    // The example of every pattern without captures parses into its own variant
+    for info in Error::VARIANTS {
+        for variant in info.variants.iter().filter(|variant| variant.captures == 0 && !variant.fallback) {
+            for pattern in variant.patterns {
+                let error = format!("{:?}", Error::from_code(info.code, &example_message(pattern)));
+                assert!(
+                    error.contains(&format!("({})", variant.name)),
+                    "{} of {} parsed into {}",
+                    pattern,
+                    variant.name,
+                    error
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn source_comments() {
+    let mut comments = SourceComments::default();
+    parse_source_comments(
+        r#"
+pub enum Error {
+    // General application defined errors
+    #[code(-1, category = General)] RPC_MISC_ERROR(MiscError), // std::exception thrown in command handling
+
+    // Wallet errors
+    #[code(-6, category = Wallet)] RPC_WALLET_INSUFFICIENT_FUNDS, // Not enough funds in wallet or account
+}
+
+pub enum MiscError {
+    // https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/blockchain.cpp#L459
+    #[patterns("^Block header missing$")]
+    BlockHeaderMissing,
+
+    #[fallback]
+    Generic(String),
+}
+
+// https://github.com/bitcoin/bitcoin/blob/v25.0/src/wallet/rpc/encrypt.cpp
+#[derive(Clone, Debug, PartialEq, EnumError)]
+pub enum WalletEncryptionFailedError {
+    #[patterns("^Error: Failed to encrypt the wallet.$")]
+    EncryptionFailed,
+
+    #[fallback]
+    Generic(String),
+}
+"#,
+        &mut comments,
+    );
+
+    assert_eq!(comments.description("RPC_MISC_ERROR"), "std::exception thrown in command handling");
+    assert_eq!(
+        comments.links("MiscError", "BlockHeaderMissing"),
+        ["https://github.com/bitcoin/bitcoin/blob/v25.0/src/rpc/blockchain.cpp#L459"]
+    );
+    assert!(comments.links("MiscError", "Generic").is_empty());
+    // Variants without links of their own share the link of the enum
+    assert_eq!(
+        comments.links("WalletEncryptionFailedError", "EncryptionFailed"),
+        ["https://github.com/bitcoin/bitcoin/blob/v25.0/src/wallet/rpc/encrypt.cpp"]
+    );
+    assert!(comments.links("WalletEncryptionFailedError", "Generic").is_empty());
+
+    let checklist = reference::checklist(Error::VARIANTS, &comments);
+    assert!(checklist.contains("### General application defined errors\n- [x] RPC_MISC_ERROR // std::exception"));
+    assert!(checklist.contains("### Wallet errors\n- [x] ~~RPC_WALLET_INSUFFICIENT_FUNDS~~ // Not enough funds"));
+}
+
+// The README and docs/ are generated, this fails once they are out of date
+#[test]
+fn reference_up_to_date() {
+    let crate_dir = Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/.."));
+    let comments = reference::read_source_comments(crate_dir).unwrap();
+    let read = |path: &str| std::fs::read_to_string(crate_dir.join(path)).unwrap();
+    let hint = "out of date, run `cargo run -p bitcoin-rpc-errors-tools --bin generate-reference`";
+
+    let readme = read("README.md");
+    let checklist = reference::checklist(Error::VARIANTS, &comments);
+    assert_eq!(reference::update_readme(&readme, &checklist).as_ref(), Some(&readme), "README.md is {}", hint);
+    assert!(read("docs/errors.md") == reference::markdown(Error::VARIANTS, &comments), "docs/errors.md is {}", hint);
+    assert!(read("docs/errors.html") == reference::html(Error::VARIANTS, &comments), "docs/errors.html is {}", hint);
+}
+
+#[test]
+fn explain_errors() {
+    let message = String::from("Requested wallet does not exist or is not loaded");
+    assert_eq!(parse_input(r#"{"code":-18,"message":"Requested wallet does not exist or is not loaded"}"#), Ok((-18, message.clone())));
+    assert_eq!(
+        parse_input(r#"{"result":null,"error":{"code":-18,"message":"Requested wallet does not exist or is not loaded"},"id":1}"#),
+        Ok((-18, message.clone()))
+    );
+    assert_eq!(
+        parse_input("error code: -18\nerror message:\nRequested wallet does not exist or is not loaded\n"),
+        Ok((-18, message))
+    );
+    assert!(parse_input(r#"{"result":0,"error":null,"id":1}"#).is_err());
+    assert!(parse_input("error: Could not connect to the server 127.0.0.1:8332").is_err());
+
+    let explanation = explain(-8, "Unknown named parameter foo", None);
+    assert_eq!(explanation.name, "RPC_INVALID_PARAMETER");
+    assert_eq!(explanation.enum_name, Some("InvalidParameterError"));
+    assert_eq!(explanation.variant, Some("UnknownNamedParameter"));
+    assert_eq!(explanation.fields, [r#""foo""#]);
+    assert_eq!(explanation.category, ErrorCategory::General);
+    assert!(!explanation.fallback);
+    assert_eq!(explanation.to_json()["fields"][0], "foo");
+    assert_eq!(explanation.remediation, None);
+    assert!(explanation.to_json()["remediation"].is_null());
+    assert_eq!(explanation.retry.class, RetryClass::Permanent);
+    assert!(explanation.to_text().contains("\nretry:    Permanent\n"));
+
+    let explanation = explain(-28, "Loading block index…", None);
+    assert!(explanation.to_text().contains("\nretry:    Transient, after 1s\n"));
+    assert_eq!(explanation.to_json()["retry"]["class"], "Transient");
+    assert_eq!(explanation.to_json()["retry"]["backoff_ms"], 1000);
+
+    let explanation = explain(-12, "Error: Keypool ran out, please call keypoolrefill first", None);
+    assert_eq!(explanation.remediation.unwrap().rpc, Some("keypoolrefill"));
+    assert!(explanation.to_text().contains("\nTry calling `keypoolrefill`\n"));
+    assert_eq!(explanation.to_json()["remediation"]["rpc"], "keypoolrefill");
+
+    let explanation = explain(-42, "Something new", None);
+    assert_eq!(explanation.name, "RPC_UNKOWN_ERROR");
+    assert_eq!(explanation.enum_name, None);
+    assert!(explanation.fallback);
+    assert!(explanation.to_text().starts_with("RPC_UNKOWN_ERROR (-42)\nvariant:  -\n"));
+}